@@ -0,0 +1,155 @@
+use crate::{
+    Diagnostic, Severity, State, StateMachine,
+    diagnostic::{is_code, parse_location, strip_ansi},
+    tsgo_state_machine::is_summary,
+};
+
+/// Parses the output of Angular's compiler, be it `ngc` or the Angular CLI.
+///
+/// `ngc` prints its diagnostics like tsc does, with `NG` codes next to the `TS` ones and
+/// templates as paths, like `src/app/billing/invoice.component.html:12:5 - error NG8002: ...`.
+///
+/// The CLI wraps them the way esbuild does, with the location following the message:
+///
+/// ```text
+/// ✘ [ERROR] NG8002: Can't bind to 'total' since it isn't a known property of 'div'. [plugin angular-compiler]
+///
+///     src/app/billing/invoice.component.html:12:5:
+///       12 │   <div [total]="sum"></div>
+///          ╵        ~~~~~~~~~~~~~~~~~
+///
+///   Error occurs in the template of component InvoiceComponent.
+/// ```
+///
+/// Until the first location shows up, the diagnostic awaits it and the pipeline holds its
+/// lines back. Locations after that one belong to the related information.
+pub struct AngularStateMachine {
+    pub state: State,
+    pub include: Vec<String>,
+    diagnostic: Option<Diagnostic>,
+    started: bool,
+    located: bool,
+    wrapped: bool,
+}
+
+impl AngularStateMachine {
+    pub fn new(include: Vec<String>) -> Self {
+        return Self {
+            state: State::ParseToPause,
+            include,
+            diagnostic: None,
+            started: false,
+            located: false,
+            wrapped: false,
+        };
+    }
+
+    /// Whether a line is how the Angular CLI reports a diagnostic, or an `ngc` diagnostic
+    /// with an `NG` code.
+    pub fn detect(line: &str) -> bool {
+        let line = strip_ansi(line);
+        return parse_wrapped(&line).is_some()
+            || parse_header(&line).is_some_and(|d| d.code.is_some_and(|c| c.starts_with("NG")));
+    }
+
+    fn start(&mut self, diagnostic: Diagnostic, wrapped: bool) {
+        self.diagnostic = Some(diagnostic);
+        self.started = true;
+        self.located = !wrapped;
+        self.wrapped = wrapped;
+    }
+}
+
+// Headers of the CLI look like `✘ [ERROR] NG8002: message [plugin angular-compiler]`,
+// with `▲ [WARNING]` for warnings and `X` instead of `✘` where there is no unicode
+fn parse_wrapped(line: &str) -> Option<Diagnostic> {
+    let (marker, rest) = line.split_once(" [")?;
+    if !matches!(marker, "✘" | "▲" | "X") {
+        return None;
+    }
+    let (severity, text) = rest.split_once("] ")?;
+
+    let mut diagnostic = Diagnostic::new("", 0, 0);
+    diagnostic.severity = Severity::parse(severity)?;
+    let text = match text.rsplit_once(" [plugin ") {
+        Some((text, _)) => text,
+        None => text,
+    };
+    match text.split_once(": ") {
+        Some((code, message)) if is_code(code) => {
+            diagnostic.code = Some(code.to_string());
+            diagnostic.message = message.to_string();
+        }
+        _ => diagnostic.message = text.to_string(),
+    }
+    return Some(diagnostic);
+}
+
+// ngc headers, like `path:12:5 - error NG8002: ...` or `path(12,5): error NG8002: ...`
+fn parse_header(line: &str) -> Option<Diagnostic> {
+    if let Some((_, message)) = line.split_once(" - ") {
+        if Severity::parse(message.split(' ').next()?).is_some() {
+            return Diagnostic::from_pretty(line);
+        }
+    }
+    return Diagnostic::from_plain(line);
+}
+
+impl StateMachine for AngularStateMachine {
+    fn run(&mut self, line: &String) -> (&State, bool) {
+        self.started = false;
+        let line = strip_ansi(line);
+        if self.state != State::End && is_summary(&line) {
+            self.state = State::End;
+        }
+        if self.state == State::End {
+            self.diagnostic = None;
+            return (&self.state, true);
+        }
+
+        if let Some(diagnostic) = parse_wrapped(&line) {
+            self.start(diagnostic, true);
+            return (&self.state, true);
+        }
+        if let Some(diagnostic) = parse_header(&line) {
+            self.start(diagnostic, false);
+            return (&self.state, true);
+        }
+
+        // Everything the CLI says about a diagnostic is indented, the first location
+        // on a line of its own being where it is, like `    src/app/app.html:12:5:`
+        if self.wrapped && !line.is_empty() && !line.starts_with(' ') {
+            self.diagnostic = None;
+        }
+        if let Some(diagnostic) = self.diagnostic.as_mut() {
+            let location = line.trim().strip_suffix(':').and_then(parse_location);
+            if let (false, Some((path, row, column))) = (self.located, location) {
+                diagnostic.path = path.to_string();
+                diagnostic.line = row;
+                diagnostic.column = column;
+                self.located = true;
+            }
+            return (&self.state, true);
+        }
+
+        // Lines that aren't part of any diagnostic, like the CLI's build progress,
+        // only make it through when we aren't filtering anything
+        return (&self.state, self.include.is_empty());
+    }
+
+    fn is_finished(&self) -> bool {
+        self.state == State::End
+    }
+
+    fn diagnostic(&self) -> Option<&Diagnostic> {
+        self.diagnostic.as_ref()
+    }
+
+    fn starts_diagnostic(&self) -> bool {
+        self.started
+    }
+
+    fn awaits_location(&self) -> bool {
+        self.diagnostic.is_some() && !self.located
+    }
+}
@@ -1,8 +1,6 @@
-use core::panic;
-
 use ansi_parser::{AnsiParser, AnsiSequence, Output};
 
-use crate::{State, StateMachine, path_matches};
+use crate::{Diagnostic, State, StateMachine, diagnostic::strip_ansi, path_matches};
 
 #[derive(PartialEq, Eq)]
 enum ParseResult {
@@ -15,6 +13,9 @@ pub struct AnsiStateMachine {
     pub identifier: AnsiSequence,
     pub state: State,
     pub include: Vec<String>,
+    diagnostic: Option<Diagnostic>,
+    started: bool,
+    error: Option<String>,
 }
 
 impl AnsiStateMachine {
@@ -23,12 +24,15 @@ impl AnsiStateMachine {
             identifier,
             state: State::ParseToPause,
             include,
+            diagnostic: None,
+            started: false,
+            error: None,
         };
     }
 
-    fn parse_line(&self, line: &String, parsing_to_pause: bool) -> ParseResult {
+    fn parse_line(&mut self, line: &str, parsing_to_pause: bool) -> ParseResult {
         //If we get an empty line, we must check if the end is nigh
-        if line.len() == 0 {
+        if line.is_empty() {
             return ParseResult::CheckEnd(self.state == State::ParseToPause);
         }
 
@@ -51,18 +55,25 @@ impl AnsiStateMachine {
             return ParseResult::KeepState;
         }
 
-        //And if it does, we extract the path from that line
-        let path = ansi_line.next().expect("Error parsing output!");
-        let path = match path {
-            Output::TextBlock(p) => p,
-            Output::Escape(_) => panic!("Error parsing output!"),
+        //And if it does, we extract the path from that line. Anything else
+        // than text right after our identifier is output we don't understand.
+        let path = match ansi_line.next() {
+            Some(Output::TextBlock(p)) => p,
+            _ => {
+                self.error = Some(format!("Expected a path after {:?}", self.identifier));
+                return ParseResult::KeepState;
+            }
         };
 
+        //Every line starting with our identifier is the header of a new diagnostic
+        self.diagnostic =
+            Some(Diagnostic::from_pretty(&strip_ansi(line)).unwrap_or(Diagnostic::new(path, 0, 0)));
+        self.started = true;
+
         //The we check if we should block it or not, and based on
         // our current state we choose the next one, essentially we flip it.
         // So if we were "parsing to pause", we now "parse to continue" and vice versa.
         let should_block = !path_matches(&self.include, path);
-        if parsing_to_pause {}
         let should_flip = match parsing_to_pause {
             true => should_block,
             false => !should_block,
@@ -73,7 +84,7 @@ impl AnsiStateMachine {
         return ParseResult::KeepState;
     }
 
-    fn process_parse_result(&mut self, line: &String, parsing_to_pause: bool) -> (&State, bool) {
+    fn process_parse_result(&mut self, line: &str, parsing_to_pause: bool) -> (&State, bool) {
         let parse_result = self.parse_line(line, parsing_to_pause);
         if let ParseResult::CheckEnd(go_back_state) = parse_result {
             self.state = State::CheckEnd(go_back_state, 1);
@@ -90,6 +101,8 @@ impl AnsiStateMachine {
 
 impl StateMachine for AnsiStateMachine {
     fn run(&mut self, line: &String) -> (&State, bool) {
+        self.started = false;
+        self.error = None;
         match self.state {
             State::ParseToPause => {
                 return self.process_parse_result(line, true);
@@ -98,7 +111,7 @@ impl StateMachine for AnsiStateMachine {
                 return self.process_parse_result(line, false);
             }
             State::CheckEnd(should_parse_to_pause, check_count) => {
-                if line.len() == 0 {
+                if line.is_empty() {
                     self.state = State::CheckEnd(should_parse_to_pause, check_count + 1);
                     return (&self.state, should_parse_to_pause);
                 }
@@ -107,13 +120,9 @@ impl StateMachine for AnsiStateMachine {
                 //   • We encountered an empty line at least twice in a row
                 //   • And after all these empty lines, the next line we encounter doesn' start with an ANSI sequence
                 if line
-                    .as_str()
                     .ansi_parse()
                     .next()
-                    .map(|i| match i {
-                        Output::TextBlock(_) => true,
-                        _ => false,
-                    })
+                    .map(|i| matches!(i, Output::TextBlock(_)))
                     .unwrap_or(false)
                     && check_count >= 2
                 {
@@ -128,6 +137,8 @@ impl StateMachine for AnsiStateMachine {
                 return self.run(line);
             }
             State::End => {
+                //Whatever follows the end is the summary, which belongs to no diagnostic
+                self.diagnostic = None;
                 return (&self.state, true);
             }
         }
@@ -136,4 +147,16 @@ impl StateMachine for AnsiStateMachine {
     fn is_finished(&self) -> bool {
         self.state == State::End
     }
+
+    fn diagnostic(&self) -> Option<&Diagnostic> {
+        self.diagnostic.as_ref()
+    }
+
+    fn starts_diagnostic(&self) -> bool {
+        self.started
+    }
+
+    fn parse_error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
 }
@@ -0,0 +1,117 @@
+use crate::{
+    Diagnostic, Severity, State, StateMachine,
+    diagnostic::{is_code, parse_location, strip_ansi},
+    tsgo_state_machine::is_summary,
+};
+
+/// Parses the output of `deno check`, which tells where a diagnostic is only after
+/// its message and code excerpt, as a `file://` URL:
+///
+/// Check file:///home/me/project/main.ts
+/// error: TS2322 [ERROR]: Type 'string' is not assignable to type 'number'.
+/// const count: number = "hello";
+///       ~~~~~
+///     at file:///home/me/project/main.ts:3:7
+///
+/// Found 1 error.
+///
+/// Until the `at` line shows up, the diagnostic awaits its location, and the pipeline
+/// holds its lines back. The path stays a URL, which the pipeline resolves when matching.
+pub struct DenoStateMachine {
+    pub state: State,
+    pub include: Vec<String>,
+    diagnostic: Option<Diagnostic>,
+    started: bool,
+    located: bool,
+}
+
+impl DenoStateMachine {
+    pub fn new(include: Vec<String>) -> Self {
+        return Self {
+            state: State::ParseToPause,
+            include,
+            diagnostic: None,
+            started: false,
+            located: false,
+        };
+    }
+
+    /// Whether a line is how `deno check` starts its output.
+    pub fn detect(line: &str) -> bool {
+        let line = strip_ansi(line);
+        return line.starts_with("Check file://") || parse_header(&line).is_some();
+    }
+}
+
+// Headers look like `TS2322 [ERROR]: Type ...`, the first one being prefixed with `error: `
+fn parse_header(line: &str) -> Option<Diagnostic> {
+    let line = line.strip_prefix("error: ").unwrap_or(line);
+    let (code, rest) = line.split_once(" [")?;
+    let (severity, message) = rest.split_once("]: ")?;
+    if !is_code(code) {
+        return None;
+    }
+
+    let mut diagnostic = Diagnostic::new("", 0, 0);
+    diagnostic.severity = Severity::parse(severity)?;
+    diagnostic.code = Some(code.to_string());
+    diagnostic.message = message.to_string();
+    return Some(diagnostic);
+}
+
+impl StateMachine for DenoStateMachine {
+    fn run(&mut self, line: &String) -> (&State, bool) {
+        self.started = false;
+        let line = strip_ansi(line);
+        if self.state != State::End && is_summary(&line) {
+            self.state = State::End;
+        }
+        if self.state == State::End {
+            self.diagnostic = None;
+            return (&self.state, true);
+        }
+
+        if let Some(diagnostic) = parse_header(&line) {
+            self.diagnostic = Some(diagnostic);
+            self.started = true;
+            self.located = false;
+            return (&self.state, true);
+        }
+
+        // The first `at` line is where the diagnostic is, the ones
+        // after it belong to its related information
+        if let Some(diagnostic) = self.diagnostic.as_mut() {
+            let location = line
+                .trim_start()
+                .strip_prefix("at ")
+                .and_then(parse_location);
+            if let (false, Some((path, row, column))) = (self.located, location) {
+                diagnostic.path = path.to_string();
+                diagnostic.line = row;
+                diagnostic.column = column;
+                self.located = true;
+            }
+            return (&self.state, true);
+        }
+
+        // Lines that aren't part of any diagnostic, like `Check file:///...`,
+        // only make it through when we aren't filtering anything
+        return (&self.state, self.include.is_empty());
+    }
+
+    fn is_finished(&self) -> bool {
+        self.state == State::End
+    }
+
+    fn diagnostic(&self) -> Option<&Diagnostic> {
+        self.diagnostic.as_ref()
+    }
+
+    fn starts_diagnostic(&self) -> bool {
+        self.started
+    }
+
+    fn awaits_location(&self) -> bool {
+        self.diagnostic.is_some() && !self.located
+    }
+}
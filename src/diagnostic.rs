@@ -0,0 +1,144 @@
+use ansi_parser::{AnsiParser, Output};
+
+/// A single compiler diagnostic, as far as we could make it out of the output.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    pub path: String,
+    pub line: usize,
+    pub column: usize,
+    pub severity: Severity,
+    /// Like `TS2322`, if the tool gives its diagnostics a code
+    pub code: Option<String>,
+    pub message: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl Severity {
+    /// Makes sense of the way tools spell out severities, like `error`, `Warn` or `WARNING`.
+    pub fn parse(text: &str) -> Option<Self> {
+        return match text.to_lowercase().as_str() {
+            "error" | "fatal" => Some(Severity::Error),
+            "warning" | "warn" => Some(Severity::Warning),
+            "info" | "hint" | "message" | "suggestion" => Some(Severity::Info),
+            _ => None,
+        };
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        return match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        };
+    }
+}
+
+impl Diagnostic {
+    pub fn new(path: &str, line: usize, column: usize) -> Self {
+        return Self {
+            path: path.to_string(),
+            line,
+            column,
+            severity: Severity::Error,
+            code: None,
+            message: String::new(),
+        };
+    }
+
+    /// Fills in severity, code and message from the part of a tsc header that
+    /// follows the location, like `error TS2322: Type 'string' is not ...`
+    pub fn with_message(mut self, text: &str) -> Self {
+        let text = text.trim();
+        let (word, rest) = text.split_once(' ').unwrap_or((text, ""));
+        let severity = match Severity::parse(word.trim_end_matches(':')) {
+            Some(severity) => severity,
+            None => {
+                self.message = text.to_string();
+                return self;
+            }
+        };
+        self.severity = severity;
+
+        let rest = rest.trim_start();
+        match rest.split_once(": ") {
+            Some((code, message)) if is_code(code) => {
+                self.code = Some(code.to_string());
+                self.message = message.to_string();
+            }
+            _ => self.message = rest.to_string(),
+        }
+        return self;
+    }
+
+    /// Parses the header of a plain `tsc` diagnostic, which looks like:
+    ///
+    /// src/features/orders/index.ts(42,15): error TS2345: Argument of type ...
+    pub fn from_plain(line: &str) -> Option<Self> {
+        // The path itself may contain parentheses, so we look for the first
+        // `(<line>,<column>): ` group instead of splitting on the first `(`
+        for (end, _) in line.match_indices("): ") {
+            let start = match line[..end].rfind('(') {
+                Some(start) => start,
+                None => continue,
+            };
+            if start == 0 {
+                continue;
+            }
+            if let Some((row, column)) = parse_position(&line[start + 1..end]) {
+                let diagnostic = Self::new(&line[..start], row, column);
+                return Some(diagnostic.with_message(&line[end + 3..]));
+            }
+        }
+        return None;
+    }
+
+    /// Parses the header of a `tsc --pretty` diagnostic once the ANSI sequences
+    /// have been stripped from it, which looks like:
+    ///
+    /// src/features/orders/index.ts:42:15 - error TS2345: Argument of type ...
+    pub fn from_pretty(line: &str) -> Option<Self> {
+        let (location, message) = line.split_once(" - ")?;
+        let (path, row, column) = parse_location(location)?;
+        return Some(Self::new(path, row, column).with_message(message));
+    }
+}
+
+/// Splits a location like `src/index.ts:42:15` into path, line and column.
+pub fn parse_location(text: &str) -> Option<(&str, usize, usize)> {
+    let (rest, column) = text.rsplit_once(':')?;
+    let (path, line) = rest.rsplit_once(':')?;
+    if path.is_empty() {
+        return None;
+    }
+    return Some((path, line.parse().ok()?, column.parse().ok()?));
+}
+
+// Codes look like `TS2322` or `NG8002`: some letters followed by digits
+pub(crate) fn is_code(text: &str) -> bool {
+    let digits = text.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+    return digits.len() < text.len()
+        && !digits.is_empty()
+        && digits.chars().all(|c| c.is_ascii_digit());
+}
+
+fn parse_position(text: &str) -> Option<(usize, usize)> {
+    let (line, column) = text.split_once(',')?;
+    return Some((line.parse().ok()?, column.parse().ok()?));
+}
+
+/// Removes every ANSI escape sequence from a line, keeping only its text.
+pub fn strip_ansi(line: &str) -> String {
+    return line
+        .ansi_parse()
+        .filter_map(|output| match output {
+            Output::TextBlock(text) => Some(text),
+            Output::Escape(_) => None,
+        })
+        .collect();
+}
@@ -0,0 +1,42 @@
+use std::{fmt, io};
+
+#[derive(Debug)]
+pub enum Error {
+    /// Reading the input or writing the output failed
+    Io(io::Error),
+    /// The state machine could not make sense of a line of input
+    Parse { line: usize, message: String },
+    /// One of the include patterns is not a valid glob
+    InvalidPattern(String),
+    /// The input format asked for is not one we know
+    UnknownInputFormat(String),
+    /// The suppressions file could not be read or has an invalid entry
+    InvalidSuppressions(String),
+    /// One of the report files could not be written
+    InvalidReportPath(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "{}", error),
+            Error::Parse { line, message } => {
+                write!(f, "Could not parse line {}: {}", line, message)
+            }
+            Error::InvalidPattern(message) => write!(f, "{}", message),
+            Error::UnknownInputFormat(name) => write!(f, "Unknown input format \"{}\"", name),
+            Error::InvalidSuppressions(message) => write!(f, "{}", message),
+            Error::InvalidReportPath(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        return Error::Io(error);
+    }
+}
@@ -0,0 +1,152 @@
+use std::io::Write;
+
+use serde_json::Value;
+
+use crate::{
+    Diagnostic, Error, Options, Outcome, Result, Severity,
+    diagnostic::strip_ansi,
+    matcher::Matcher,
+    render::{Render, Renderer},
+    report::{self, Context},
+};
+
+/// What the format is called, as given to `--input-format`.
+pub const NAME: &str = "eslint-json";
+
+/// Whether a line is how ESLint's `json` formatter starts its output.
+pub fn detect(line: &str) -> bool {
+    let line = line.trim_start();
+    return line.starts_with("[{\"filePath\"") || line.trim_end() == "[]";
+}
+
+/// Filters the output of ESLint's `json` formatter, which comes as a whole instead
+/// of line by line. What makes it through is written back as the same kind of JSON,
+/// with the counts of every file adjusted and the files left without problems dropped,
+/// so that it can still be fed to whatever reads ESLint's output. With `Options::render`,
+/// the kept messages get written in that style instead, and with `Options::report`,
+/// the report gets written.
+pub fn filter<W: Write>(input: &str, mut writer: W, options: &Options) -> Result<Outcome> {
+    let mut results: Vec<Value> = serde_json::from_str(input).map_err(|error| Error::Parse {
+        line: error.line(),
+        message: format!("Invalid ESLint JSON output: {}", error),
+    })?;
+
+    let mut matcher = Matcher::new(options)?;
+    let mut kept = Vec::new();
+    for result in &mut results {
+        let path = result["filePath"].as_str().unwrap_or_default().to_string();
+        if let Some(messages) = result["messages"].as_array_mut() {
+            messages.retain(|message| {
+                let diagnostic = diagnostic(&path, message);
+                let keep = matcher.admit(&diagnostic);
+                if keep {
+                    kept.push(diagnostic);
+                }
+                return keep;
+            });
+        }
+        recount(result);
+    }
+    results.retain(|result| result["messages"].as_array().is_some_and(|m| !m.is_empty()));
+
+    // Reports take precedence over rendering, like they do for other input
+    let render = options.render.filter(|_| options.report.is_none());
+    if let Some(style) = render {
+        write_rendered(&kept, style, &matcher, &mut writer, options)?;
+    }
+    // JSON and reports would break with the stale suppressions in them
+    let stale = matcher.write_stale_suppressions(options, &mut writer, render.is_none())?;
+    let reported: Vec<Diagnostic> = kept
+        .into_iter()
+        .map(|diagnostic| Diagnostic {
+            path: report::path(matcher.normalizer(), &diagnostic.path, options),
+            ..diagnostic
+        })
+        .collect();
+
+    let outcome = matcher.outcome(stale);
+    let context = Context {
+        source: "eslint",
+        ..Context::new(options, &outcome, matcher.normalizer())
+    };
+    report::write_files(&options.reports, &reported, &context)?;
+    if let Some(format) = options.report {
+        report::write(format, &reported, &context, &mut writer)?;
+    } else if render.is_none() && !results.is_empty() {
+        let json = serde_json::to_string(&results).expect("JSON values serialize");
+        writeln!(writer, "{}", json)?;
+    }
+    writer.flush()?;
+    return Ok(outcome);
+}
+
+// Paths are written the way ESLint gives them, or relative to `output_base`, like when
+// rendering the output of other tools
+fn write_rendered<W: Write>(
+    diagnostics: &[Diagnostic],
+    style: Render,
+    matcher: &Matcher,
+    writer: &mut W,
+    options: &Options,
+) -> Result<()> {
+    let normalizer = matcher.normalizer();
+    let mut renderer = Renderer::new(style);
+    for diagnostic in diagnostics {
+        let path = match &options.output_base {
+            Some(base) if !diagnostic.path.is_empty() => {
+                normalizer.relative_to(&diagnostic.path, base)
+            }
+            _ => diagnostic.path.clone(),
+        };
+        let source = normalizer.absolute(&diagnostic.path);
+        for line in renderer.render(diagnostic, &[], &path, &source) {
+            let line = match options.no_color {
+                true => strip_ansi(&line),
+                false => line,
+            };
+            writeln!(writer, "{}", line)?;
+        }
+    }
+    if let (Some(summary), false) = (matcher.summary(), style == Render::Quickfix) {
+        writeln!(writer, "{}", summary)?;
+    }
+    return Ok(());
+}
+
+fn diagnostic(path: &str, message: &Value) -> Diagnostic {
+    let position = |key: &str| message[key].as_u64().unwrap_or_default() as usize;
+    let mut diagnostic = Diagnostic::new(path, position("line"), position("column"));
+    diagnostic.severity = match message["severity"].as_u64() {
+        Some(2) => Severity::Error,
+        _ => Severity::Warning,
+    };
+    diagnostic.code = message["ruleId"].as_str().map(|rule| rule.to_string());
+    diagnostic.message = message["message"].as_str().unwrap_or_default().to_string();
+    return diagnostic;
+}
+
+// ESLint keeps a few counts next to the messages of every file
+fn recount(result: &mut Value) {
+    let Some(messages) = result["messages"].as_array() else {
+        return;
+    };
+    let count = |severity: u64, matches: &dyn Fn(&Value) -> bool| {
+        let count = messages
+            .iter()
+            .filter(|m| m["severity"].as_u64() == Some(severity) && matches(m))
+            .count();
+        return Value::from(count);
+    };
+    let counts = [
+        ("errorCount", count(2, &|_| true)),
+        ("fatalErrorCount", count(2, &|m| m["fatal"] == true)),
+        ("warningCount", count(1, &|_| true)),
+        ("fixableErrorCount", count(2, &|m| !m["fix"].is_null())),
+        ("fixableWarningCount", count(1, &|m| !m["fix"].is_null())),
+    ];
+    for (key, count) in counts {
+        if result.get(key).is_some() {
+            result[key] = count;
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{
+    Diagnostic, Severity, State, StateMachine,
+    diagnostic::{parse_location, strip_ansi},
+    paths::is_absolute,
+};
+
+/// Parses the output of ESLint's `stylish` and `unix` formatters, giving each problem
+/// its rule as the code.
+///
+/// `stylish`, the default, prints the file name above the problems found in it, which
+/// makes it a heading, and ends with a summary:
+///
+/// /home/me/project/src/index.ts
+///    3:7   error    'count' is assigned a value but never used  no-unused-vars
+///   10:15  warning  Unexpected any. Specify a different type    @typescript-eslint/no-explicit-any
+///
+/// ✖ 2 problems (1 error, 1 warning)
+///
+/// `unix` prints one problem per line, with the severity and rule at its end:
+///
+/// /home/me/project/src/index.ts:3:7: 'count' is assigned a value but never used. [Error/no-unused-vars]
+///
+/// 1 problem
+pub struct EslintStateMachine {
+    pub state: State,
+    pub include: Vec<String>,
+    diagnostic: Option<Diagnostic>,
+    heading: bool,
+    path: String,
+}
+
+impl EslintStateMachine {
+    pub fn new(include: Vec<String>) -> Self {
+        return Self {
+            state: State::ParseToPause,
+            include,
+            diagnostic: None,
+            heading: false,
+            path: String::new(),
+        };
+    }
+
+    /// Whether a line is how the `stylish` or `unix` formatter start their output,
+    /// not counting the blank line `stylish` starts with.
+    pub fn detect(line: &str) -> bool {
+        let line = strip_ansi(line);
+        return is_file_name(&line) || parse_unix(&line).is_some();
+    }
+}
+
+// stylish prints absolute paths on a line of their own, unlike tools that
+// follow the path with a location and a message
+fn is_file_name(line: &str) -> bool {
+    return !line.starts_with(' ')
+        && is_absolute(line)
+        && !line.contains(": ")
+        && parse_location(line).is_none();
+}
+
+// Rows look like `  3:7  error  message  rule`, with the rule left out for
+// problems that don't come from one, like parsing errors
+fn parse_stylish(path: &str, line: &str) -> Option<Diagnostic> {
+    let line = line.strip_prefix(' ')?.trim_start();
+    let (position, rest) = line.split_once(' ')?;
+    let (row, column) = position.split_once(':')?;
+    let (row, column) = (row.parse().ok()?, column.parse().ok()?);
+    let rest = rest.trim_start();
+    let (severity, rest) = rest.split_once(' ')?;
+
+    let mut diagnostic = Diagnostic::new(path, row, column);
+    diagnostic.severity = Severity::parse(severity)?;
+    let rest = rest.trim();
+    match rest.rsplit_once("  ") {
+        Some((message, rule)) if !rule.contains(' ') => {
+            diagnostic.message = message.trim_end().to_string();
+            diagnostic.code = Some(rule.to_string());
+        }
+        _ => diagnostic.message = rest.to_string(),
+    }
+    return Some(diagnostic);
+}
+
+// Lines look like `src/index.ts:3:7: message [Error/rule]`
+fn parse_unix(line: &str) -> Option<Diagnostic> {
+    let (message, tag) = line.strip_suffix(']')?.rsplit_once(" [")?;
+    let (severity, rule) = match tag.split_once('/') {
+        Some((severity, rule)) => (severity, Some(rule)),
+        None => (tag, None),
+    };
+
+    // The path may contain `: ` itself, so we look for the first one that follows a location
+    for (end, _) in message.match_indices(": ") {
+        if let Some((path, row, column)) = parse_location(&message[..end]) {
+            let mut diagnostic = Diagnostic::new(path, row, column);
+            diagnostic.severity = Severity::parse(severity)?;
+            diagnostic.code = rule.map(|rule| rule.to_string());
+            diagnostic.message = message[end + 2..].to_string();
+            return Some(diagnostic);
+        }
+    }
+    return None;
+}
+
+// Both formatters sum up with something like `✖ 2 problems (1 error, 1 warning)` or `2 problems`
+fn is_summary(line: &str) -> bool {
+    let line = line.trim_start_matches(['✖', ' ']);
+    let Some((count, rest)) = line.split_once(' ') else {
+        return false;
+    };
+    return count.chars().all(|c| c.is_ascii_digit()) && rest.starts_with("problem");
+}
+
+impl StateMachine for EslintStateMachine {
+    fn run(&mut self, line: &String) -> (&State, bool) {
+        self.diagnostic = None;
+        self.heading = false;
+        let line = strip_ansi(line);
+        if self.state != State::End && is_summary(&line) {
+            self.state = State::End;
+        }
+        if self.state == State::End {
+            return (&self.state, true);
+        }
+
+        if let Some(diagnostic) = parse_unix(&line) {
+            self.diagnostic = Some(diagnostic);
+        } else if let Some(diagnostic) = parse_stylish(&self.path, &line) {
+            self.diagnostic = Some(diagnostic);
+        } else if line.trim().is_empty() {
+            self.heading = true;
+        } else if is_file_name(&line) {
+            self.path = line.trim_end().to_string();
+            self.heading = true;
+        }
+
+        // Lines we can't make sense of only make it through when we aren't filtering anything
+        return (&self.state, self.include.is_empty());
+    }
+
+    fn is_finished(&self) -> bool {
+        self.state == State::End
+    }
+
+    fn diagnostic(&self) -> Option<&Diagnostic> {
+        self.diagnostic.as_ref()
+    }
+
+    // Every problem takes a single line
+    fn starts_diagnostic(&self) -> bool {
+        self.diagnostic.is_some()
+    }
+
+    fn is_heading(&self) -> bool {
+        self.heading
+    }
+}
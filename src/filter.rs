@@ -0,0 +1,145 @@
+use std::io::{BufRead, Write};
+
+use crate::{
+    Error, Options, Pipeline, Registry, Result, StateMachine, Status, check_patterns, eslint_json,
+    matcher::Matcher,
+    pattern_state_machine::PatternStateMachine,
+    report::{self, Context},
+};
+
+/// What filtering some compiler output came down to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    /// Either `Status::Errors` if any error matched the filters, or `Status::Clean`
+    pub verdict: Status,
+    /// Diagnostics found in the input
+    pub total: usize,
+    /// Diagnostics that matched the include patterns and codes, leaving aside
+    /// duplicates and suppressed ones
+    pub matched: usize,
+    /// Matched diagnostics left out for being the same as one before, with `dedupe`
+    pub duplicates: usize,
+    /// Matched diagnostics left out because of a `tsef-ignore` comment or an entry
+    /// of the suppressions file
+    pub suppressed: usize,
+    /// Matched diagnostics that got printed
+    pub shown: usize,
+    /// Matched diagnostics left out because of `max_errors` or `max_per_file`
+    pub hidden: usize,
+}
+
+impl Default for Outcome {
+    fn default() -> Self {
+        return Self {
+            verdict: Status::Clean,
+            total: 0,
+            matched: 0,
+            duplicates: 0,
+            suppressed: 0,
+            shown: 0,
+            hidden: 0,
+        };
+    }
+}
+
+/// Filters the compiler output read from `reader` and writes whatever makes it
+/// through to `writer`, picking the right state machine for the input on the way.
+pub fn filter<R: BufRead, W: Write>(reader: R, writer: W, options: &Options) -> Result<Outcome> {
+    return filter_with(reader, writer, options, &Registry::default());
+}
+
+/// Same as `filter`, picking the state machine out of the given registry instead
+/// of the default one, for reading the output of tools tsef doesn't know about.
+pub fn filter_with<R: BufRead, W: Write>(
+    reader: R,
+    mut writer: W,
+    options: &Options,
+    registry: &Registry,
+) -> Result<Outcome> {
+    check_patterns(&options.include)?;
+    let pattern = match &options.pattern {
+        Some(pattern) => Some(PatternStateMachine::new(
+            pattern,
+            options.continuation.as_deref(),
+            options.include.clone(),
+        )?),
+        None => None,
+    };
+    if let Some(name) = &options.input_format {
+        if !registry.knows(name) {
+            return Err(Error::UnknownInputFormat(name.clone()));
+        }
+    }
+    report::check_files(&options.reports)?;
+
+    // Some tools start their output with blank lines, so we look past
+    // them for the first line that tells what we are reading
+    let mut lines = reader.lines();
+    let mut blank_lines = Vec::new();
+    let first_line = loop {
+        match lines.next().transpose()? {
+            Some(line) if line.trim().is_empty() => blank_lines.push(line),
+            line => break line,
+        }
+    };
+    // If receive no input, then we treat it as compilation being successful
+    if first_line.is_none() && blank_lines.is_empty() {
+        // Though the suppressions still get checked, expired ones failing even then
+        let mut matcher = Matcher::new(options)?;
+        let aside = options.report.is_some();
+        let stale = matcher.write_stale_suppressions(options, &mut writer, aside)?;
+        let outcome = matcher.outcome(stale);
+        let context = Context::new(options, &outcome, matcher.normalizer());
+        report::write_files(&options.reports, &[], &context)?;
+        if let Some(format) = options.report {
+            report::write(format, &[], &context, &mut writer)?;
+        }
+        writer.flush()?;
+        return Ok(outcome);
+    }
+    let first_line = first_line.unwrap_or_default();
+    let lines = blank_lines
+        .into_iter()
+        .chain([first_line.clone()])
+        .map(Ok)
+        .chain(lines);
+
+    if let Some(sm) = pattern {
+        return run(Box::new(sm), lines, writer, options);
+    }
+
+    // Unless told otherwise, we go by the first line. ESLint's JSON only makes sense as a whole
+    let name = options.input_format.as_deref();
+    if name == Some(eslint_json::NAME) || (name.is_none() && eslint_json::detect(&first_line)) {
+        let input = lines.collect::<std::io::Result<Vec<String>>>()?.join("\n");
+        return eslint_json::filter(&input, writer, options);
+    }
+    let parser = match name {
+        Some(name) => registry.get(name),
+        None => registry.detect(&first_line),
+    };
+    let parser =
+        parser.ok_or_else(|| Error::UnknownInputFormat(name.unwrap_or("tsc").to_string()))?;
+    let sm = parser.state_machine(&first_line, options.include.clone());
+    // Reports name the tool the diagnostics came from
+    let options = Options {
+        input_format: Some(parser.name().to_string()),
+        ..options.clone()
+    };
+    return run(sm, lines, writer, &options);
+}
+
+fn run<W: Write>(
+    sm: Box<dyn StateMachine>,
+    lines: impl Iterator<Item = std::io::Result<String>>,
+    writer: W,
+    options: &Options,
+) -> Result<Outcome> {
+    // We keep reading past the end instead of stopping, so that whatever
+    // writes to us never gets cut off halfway through its output
+    let mut pipeline = Pipeline::new(sm, writer, options)?;
+    for line in lines {
+        pipeline.push(&line?)?;
+    }
+    return pipeline.finish();
+}
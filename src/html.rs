@@ -0,0 +1,266 @@
+use std::{collections::BTreeMap, collections::HashMap, io::Write, path::Path};
+
+use crate::{
+    Diagnostic, Outcome,
+    markdown::{counts, title},
+    report::by_file,
+};
+
+// Lines of code shown above and below the one a diagnostic points at
+const EXCERPT_CONTEXT: usize = 2;
+
+/// Writes a single HTML page about the diagnostics, to be opened without anything else:
+/// a tree of the files with the count of every directory, a search box for codes, and
+/// each diagnostic along with the lines of code around it, read from `base`.
+pub fn write<W: Write>(
+    diagnostics: &[Diagnostic],
+    outcome: &Outcome,
+    base: &str,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    let mut files = by_file(diagnostics);
+    files.sort_by_key(|(path, _)| *path);
+    let title = title(diagnostics, files.len());
+
+    writeln!(writer, "<!DOCTYPE html>")?;
+    writeln!(writer, "<html lang=\"en\">")?;
+    writeln!(writer, "<head>")?;
+    writeln!(writer, "<meta charset=\"utf-8\">")?;
+    writeln!(writer, "<title>tsef: {}</title>", escape(&title))?;
+    writeln!(writer, "<style>{}</style>", STYLE)?;
+    writeln!(writer, "</head>")?;
+    writeln!(writer, "<body>")?;
+    writeln!(writer, "<header>")?;
+    writeln!(writer, "<h1>{}</h1>", escape(&title))?;
+    if let Some(counts) = counts(outcome) {
+        writeln!(writer, "<p>{}</p>", escape(&counts))?;
+    }
+    writeln!(
+        writer,
+        "<input id=\"search\" type=\"search\" placeholder=\"Search by code, like TS2322\"> <span id=\"shown\"></span>"
+    )?;
+    writeln!(writer, "</header>")?;
+
+    writeln!(writer, "<nav>")?;
+    let mut tree = Directory::default();
+    for (index, (path, diagnostics)) in files.iter().enumerate() {
+        tree.insert(path, index, diagnostics.len());
+    }
+    tree.write(writer)?;
+    writeln!(writer, "</nav>")?;
+
+    writeln!(writer, "<main>")?;
+    let mut sources = HashMap::new();
+    for (index, (path, diagnostics)) in files.iter().enumerate() {
+        writeln!(writer, "<section class=\"file\" id=\"file-{}\">", index)?;
+        writeln!(
+            writer,
+            "<h2>{} <span class=\"count\">{}</span></h2>",
+            escape(display(path)),
+            diagnostics.len()
+        )?;
+        let source = sources
+            .entry(*path)
+            .or_insert_with(|| read_source(base, path));
+        for diagnostic in diagnostics {
+            write_diagnostic(diagnostic, source.as_deref(), writer)?;
+        }
+        writeln!(writer, "</section>")?;
+    }
+    writeln!(writer, "</main>")?;
+    writeln!(writer, "<script>{}</script>", SCRIPT)?;
+    writeln!(writer, "</body>")?;
+    writeln!(writer, "</html>")?;
+    return Ok(());
+}
+
+fn write_diagnostic<W: Write>(
+    diagnostic: &Diagnostic,
+    source: Option<&[String]>,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    let code = diagnostic.code.as_deref().unwrap_or_default();
+    let severity = diagnostic.severity.as_str();
+    writeln!(
+        writer,
+        "<article class=\"diagnostic {}\" data-code=\"{}\">",
+        severity,
+        escape(code)
+    )?;
+    write!(
+        writer,
+        "<p><span class=\"position\">{}:{}</span> <span class=\"severity\">{}</span>",
+        diagnostic.line, diagnostic.column, severity
+    )?;
+    if !code.is_empty() {
+        write!(writer, " <code>{}</code>", escape(code))?;
+    }
+    writeln!(
+        writer,
+        " {}</p>",
+        escape(&diagnostic.message).replace('\n', "<br>")
+    )?;
+    if let Some(excerpt) = source.and_then(|source| excerpt(diagnostic, source)) {
+        writeln!(writer, "<pre>{}</pre>", excerpt)?;
+    }
+    writeln!(writer, "</article>")?;
+    return Ok(());
+}
+
+// The lines around the one the diagnostic points at, marking its column with a caret
+fn excerpt(diagnostic: &Diagnostic, source: &[String]) -> Option<String> {
+    if diagnostic.line == 0 || diagnostic.line > source.len() {
+        return None;
+    }
+    let first = diagnostic.line.saturating_sub(EXCERPT_CONTEXT).max(1);
+    let last = (diagnostic.line + EXCERPT_CONTEXT).min(source.len());
+    let width = last.to_string().len();
+
+    let mut lines = Vec::new();
+    for number in first..=last {
+        let text = &source[number - 1];
+        let class = if number == diagnostic.line {
+            " hit"
+        } else {
+            ""
+        };
+        lines.push(format!(
+            "<span class=\"line{}\">{:>width$} | {}</span>",
+            class,
+            number,
+            escape(text)
+        ));
+        if number == diagnostic.line && diagnostic.column > 0 {
+            // Tabs stay tabs, so that the caret lines up however wide they show
+            let indent: String = text
+                .chars()
+                .take(diagnostic.column - 1)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            lines.push(format!(
+                "<span class=\"caret\">{:>width$} | {}^</span>",
+                "", indent
+            ));
+        }
+    }
+    return Some(lines.join("\n"));
+}
+
+fn read_source(base: &str, path: &str) -> Option<Vec<String>> {
+    if path.is_empty() {
+        return None;
+    }
+    let text = std::fs::read_to_string(Path::new(base).join(path)).ok()?;
+    return Some(text.lines().map(|line| line.to_string()).collect());
+}
+
+fn display(path: &str) -> &str {
+    return if path.is_empty() { "(no file)" } else { path };
+}
+
+// The files with diagnostics, grouped by directory
+#[derive(Default)]
+struct Directory {
+    count: usize,
+    directories: BTreeMap<String, Directory>,
+    files: BTreeMap<String, (usize, usize)>,
+}
+
+impl Directory {
+    fn insert(&mut self, path: &str, index: usize, count: usize) {
+        self.count += count;
+        let path = display(path);
+        match path.split_once('/') {
+            Some((directory, rest)) if !rest.is_empty() => {
+                let directory = if directory.is_empty() { "/" } else { directory };
+                self.directories
+                    .entry(directory.to_string())
+                    .or_default()
+                    .insert(rest, index, count);
+            }
+            _ => {
+                self.files.insert(path.to_string(), (index, count));
+            }
+        }
+    }
+
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writeln!(writer, "<ul>")?;
+        for (name, directory) in &self.directories {
+            // Directories holding nothing but another directory read as one, like src/components
+            let mut name = name.clone();
+            let mut directory = directory;
+            while directory.files.is_empty() && directory.directories.len() == 1 {
+                let (child, grandchild) = directory.directories.iter().next().expect("One child");
+                name = format!("{}/{}", name.trim_end_matches('/'), child);
+                directory = grandchild;
+            }
+            writeln!(
+                writer,
+                "<li><details open><summary>{}/ <span class=\"count\">{}</span></summary>",
+                escape(name.trim_end_matches('/')),
+                directory.count
+            )?;
+            directory.write(writer)?;
+            writeln!(writer, "</details></li>")?;
+        }
+        for (name, (index, count)) in &self.files {
+            writeln!(
+                writer,
+                "<li><a href=\"#file-{}\">{}</a> <span class=\"count\">{}</span></li>",
+                index,
+                escape(name),
+                count
+            )?;
+        }
+        writeln!(writer, "</ul>")?;
+        return Ok(());
+    }
+}
+
+fn escape(text: &str) -> String {
+    return text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;");
+}
+
+const STYLE: &str = "
+body { margin: 0; font: 14px/1.5 system-ui, sans-serif; color: #1f2328; display: grid; grid-template: auto 1fr / minmax(14em, 22em) 1fr; height: 100vh; }
+header { grid-column: 1 / 3; padding: 0.5em 1.5em; border-bottom: 1px solid #d0d7de; }
+h1 { font-size: 1.4em; margin: 0.3em 0; }
+nav { overflow: auto; padding: 0.5em 1em; border-right: 1px solid #d0d7de; }
+nav ul { list-style: none; padding-left: 1em; margin: 0; }
+nav > ul { padding-left: 0; }
+main { overflow: auto; padding: 0 1.5em; }
+h2 { font-size: 1.1em; font-family: ui-monospace, monospace; margin-top: 1.5em; }
+.count { color: #59636e; font-size: 0.85em; }
+.diagnostic { border-left: 3px solid #cf222e; padding-left: 0.8em; margin: 1em 0; }
+.diagnostic.warning { border-color: #bf8700; }
+.diagnostic.info { border-color: #0969da; }
+.position { font-family: ui-monospace, monospace; color: #59636e; }
+.severity { font-weight: 600; }
+pre { background: #f6f8fa; padding: 0.5em; overflow: auto; tab-size: 4; }
+.hit { font-weight: 600; }
+.caret { color: #cf222e; }
+[hidden] { display: none !important; }
+";
+
+const SCRIPT: &str = "
+const search = document.getElementById('search');
+search.addEventListener('input', () => {
+  const query = search.value.trim().toLowerCase();
+  let shown = 0;
+  for (const file of document.querySelectorAll('section.file')) {
+    let visible = 0;
+    for (const diagnostic of file.querySelectorAll('.diagnostic')) {
+      diagnostic.hidden = !diagnostic.dataset.code.toLowerCase().includes(query);
+      visible += diagnostic.hidden ? 0 : 1;
+    }
+    file.hidden = visible === 0;
+    shown += visible;
+  }
+  document.getElementById('shown').textContent = query ? shown + ' shown' : '';
+});
+";
@@ -1,3 +1,5 @@
+#![allow(clippy::needless_return)]
+
 use std::path::PathBuf;
 
 use path_matchers::{PathMatcher, glob};
@@ -28,7 +30,7 @@ use path_matchers::{PathMatcher, glob};
  * • CheckEnd → ParseToContinue
  * • CheckEnd → End
  */
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum State {
     ParseToPause,
     ParseToContinue,
@@ -36,16 +38,89 @@ pub enum State {
     End,
 }
 
+/// Reads the output of a tool line by line, telling which lines to print and
+/// which diagnostic they belong to. Implement it along with a `DiagnosticParser`
+/// to read the output of tools tsef doesn't know about.
 pub trait StateMachine {
+    /// Whether the diagnostics are over and what's left is the tool's summary,
+    /// which only gets printed with `show_full`.
     fn is_finished(&self) -> bool;
+
+    /// Takes the next line, returning the state we are in after it and whether the line
+    /// should be printed. Lines belonging to a diagnostic are printed depending on the
+    /// diagnostic instead, so this only matters for lines outside of them.
+    #[allow(clippy::ptr_arg)]
     fn run(&mut self, line: &String) -> (&State, bool);
+
+    /// The diagnostic that the last line passed to `run` belongs to, if any.
+    fn diagnostic(&self) -> Option<&Diagnostic> {
+        None
+    }
+
+    /// Whether the last line passed to `run` was the first line of a diagnostic.
+    fn starts_diagnostic(&self) -> bool {
+        false
+    }
+
+    /// Whether the current diagnostic is still waiting to tell where it is, for tools
+    /// that print the location after the message, or what it is about, for tools that
+    /// print the location on a line of its own. Until it does, its lines are held back.
+    fn awaits_location(&self) -> bool {
+        false
+    }
+
+    /// Whether the last line passed to `run` heads the diagnostics below it, like the
+    /// file name ESLint prints above its problems. Headings are only printed along with
+    /// the first kept diagnostic under them.
+    fn is_heading(&self) -> bool {
+        false
+    }
+
+    /// Why the last line passed to `run` could not be parsed, if it couldn't.
+    fn parse_error(&self) -> Option<&str> {
+        None
+    }
 }
 
+pub mod angular_state_machine;
 pub mod ansi_state_machine;
+pub mod deno_state_machine;
+pub mod diagnostic;
+pub mod error;
+pub mod eslint_json;
+pub mod eslint_state_machine;
+pub mod filter;
+pub mod html;
+pub mod limits;
+pub mod markdown;
+pub mod matcher;
+pub mod options;
+pub mod paths;
+pub mod pattern_state_machine;
+pub mod pipeline;
+pub mod registry;
+pub mod render;
+pub mod report;
 pub mod simple_state_machine;
+pub mod status;
+pub mod suppression_file;
+pub mod suppressions;
+pub mod svelte_check_state_machine;
+pub mod tsgo_state_machine;
+pub mod vue_tsc_state_machine;
+
+pub use diagnostic::{Diagnostic, Severity};
+pub use error::{Error, Result};
+pub use filter::{Outcome, filter, filter_with};
+pub use options::Options;
+pub use pipeline::Pipeline;
+pub use registry::{DiagnosticParser, Registry};
+pub use render::Render;
+pub use report::Report;
+pub use status::Status;
 
 pub fn path_matches(list: &Vec<String>, path: &str) -> bool {
-    if list.len() == 0 {
+    if list.is_empty() {
         return true;
     }
     for item in list {
@@ -58,3 +133,17 @@ pub fn path_matches(list: &Vec<String>, path: &str) -> bool {
 
     return false;
 }
+
+/// Makes sure every glob pattern in the list can be compiled, so that
+/// `path_matches` doesn't have to give up halfway through the input.
+pub fn check_patterns(list: &[String]) -> Result<()> {
+    for item in list {
+        if let Err(error) = glob(item.as_str()) {
+            return Err(Error::InvalidPattern(format!(
+                "Invalid glob pattern \"{}\": {}",
+                item, error
+            )));
+        }
+    }
+    return Ok(());
+}
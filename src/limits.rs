@@ -0,0 +1,75 @@
+use std::collections::{HashMap, HashSet};
+
+/// Caps how many diagnostics get printed, overall and per file, while still
+/// keeping count of the ones that didn't make the cut.
+#[derive(Default)]
+pub struct Limits {
+    pub max_errors: Option<usize>,
+    pub max_per_file: Option<usize>,
+    shown: usize,
+    shown_per_file: HashMap<String, usize>,
+    hidden: usize,
+    hidden_files: HashSet<String>,
+}
+
+impl Limits {
+    pub fn new(max_errors: Option<usize>, max_per_file: Option<usize>) -> Self {
+        return Self {
+            max_errors,
+            max_per_file,
+            ..Default::default()
+        };
+    }
+
+    /// Records a diagnostic from `path` and tells whether it fits within the limits.
+    pub fn admit(&mut self, path: &str) -> bool {
+        let shown_in_file = self.shown_per_file.get(path).copied().unwrap_or(0);
+        let over_total = self.max_errors.is_some_and(|max| self.shown >= max);
+        let over_file = self.max_per_file.is_some_and(|max| shown_in_file >= max);
+        if over_total || over_file {
+            self.hidden += 1;
+            self.hidden_files.insert(path.to_string());
+            return false;
+        }
+
+        self.shown += 1;
+        self.shown_per_file
+            .insert(path.to_string(), shown_in_file + 1);
+        return true;
+    }
+
+    pub fn shown(&self) -> usize {
+        self.shown
+    }
+
+    pub fn hidden(&self) -> usize {
+        self.hidden
+    }
+
+    /// The line telling how much was left out, e.g. "…and 19,950 more errors in 812 files".
+    pub fn summary(&self) -> Option<String> {
+        if self.hidden == 0 {
+            return None;
+        }
+        let files = self.hidden_files.len();
+        return Some(format!(
+            "…and {} more {} in {} {}",
+            group_digits(self.hidden),
+            if self.hidden == 1 { "error" } else { "errors" },
+            group_digits(files),
+            if files == 1 { "file" } else { "files" },
+        ));
+    }
+}
+
+pub(crate) fn group_digits(number: usize) -> String {
+    let digits = number.to_string();
+    let mut grouped = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    return grouped;
+}
@@ -1,14 +1,32 @@
-use std::process::ExitCode;
+#![allow(clippy::needless_return)]
 
-use ansi_parser::{AnsiParser, Output};
-use clap::{Parser, command};
+use std::{
+    io::{BufRead, BufReader, BufWriter, IsTerminal},
+    path::Path,
+    process::{Child, Command, ExitCode, Stdio},
+};
+
+use clap::{Parser, Subcommand};
 use tsef::{
-    StateMachine, ansi_state_machine::AnsiStateMachine, simple_state_machine::SimpleStateMachine,
+    Error, Options, Registry, Render, Report, Status, check_patterns,
+    pattern_state_machine::PatternStateMachine, render::ERRORFORMAT,
 };
 
+const EXIT_CODES: &str = "\
+Exit codes:
+  0  No errors left after filtering
+  1  Errors matched the filters
+  2  Invalid arguments or configuration
+  3  The input could not be read or parsed, or the output could not be written
+  4  The command given after -- crashed or could not be started";
+
 #[derive(Parser, Debug)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, after_help = EXIT_CODES)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
+    #[command(subcommand)]
+    action: Option<Action>,
+
     #[arg(
         short,
         long,
@@ -16,6 +34,22 @@ struct Args {
     )]
     include: Vec<String>,
 
+    #[arg(
+        long = "code",
+        value_name = "CODE",
+        value_delimiter = ',',
+        help = "Only keep diagnostics with this code, like TS2322 or an ESLint rule such as no-console"
+    )]
+    codes: Vec<String>,
+
+    #[arg(
+        long = "exclude-code",
+        value_name = "CODE",
+        value_delimiter = ',',
+        help = "Leave out diagnostics with this code"
+    )]
+    exclude_codes: Vec<String>,
+
     #[arg(
         short,
         long,
@@ -23,62 +57,293 @@ struct Args {
         help = "Show the summary output as well when using --pretty with tsc"
     )]
     show_full: bool,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Stop printing after N errors, only counting the rest"
+    )]
+    max_errors: Option<usize>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Print at most N errors per file, only counting the rest"
+    )]
+    max_per_file: Option<usize>,
+
+    #[arg(
+        long,
+        value_name = "DIR",
+        help = "Directory the include patterns are relative to. Defaults to the current one"
+    )]
+    root: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "DIR",
+        help = "Rewrite the paths of printed errors to be relative to this directory"
+    )]
+    output_base: Option<String>,
+
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "Collect all errors and print them sorted by file at the end, instead of as they come"
+    )]
+    buffer: bool,
+
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "Leave out errors with the same file, position, code and message as one printed before"
+    )]
+    dedupe: bool,
+
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "List the tsef-ignore comments and suppressions file entries that no longer suppress anything, failing if there are any"
+    )]
+    report_unused_suppressions: bool,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "File listing reviewed suppressions with their owners and expiry dates. Defaults to tsef-suppressions.toml under the root, if there is one"
+    )]
+    suppressions: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Tool whose output is read: tsc, vue-tsc, svelte-check, tsgo, deno, angular, eslint or eslint-json. Detected from the output by default"
+    )]
+    input_format: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "REGEX",
+        help = "Read diagnostics with this regular expression instead of a known format, naming its groups file, line, col, severity, code and message"
+    )]
+    pattern: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "REGEX",
+        requires = "pattern",
+        help = "Lines matching this regular expression continue the diagnostic above them. Defaults to indented lines"
+    )]
+    continuation: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        default_value = "text",
+        value_parser = ["text", "quickfix", "checkstyle", "gitlab", "rdjson", "rdjsonl", "markdown", "html", "sarif", "junit"],
+        help = "Write the errors as they come, as a quickfix list with one path:line:col: line per error for editors, or as a Checkstyle XML, GitLab Code Quality, reviewdog rdjson/rdjsonl, Markdown, HTML, SARIF or JUnit XML report"
+    )]
+    format: String,
+
+    #[arg(
+        long = "report",
+        value_name = "FORMAT=PATH",
+        value_parser = parse_report,
+        help = "Also write a report to a file, like markdown=summary.md. Can be given several times"
+    )]
+    reports: Vec<(Report, String)>,
+
+    #[arg(
+        long,
+        value_name = "STYLE",
+        value_parser = ["pretty", "plain"],
+        conflicts_with = "format",
+        help = "Write the kept errors again like tsc --pretty does, code frames included, or like plain tsc"
+    )]
+    render: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "WHEN",
+        default_value = "auto",
+        value_parser = ["auto", "always", "never"],
+        help = "Keep the colors of the output. With auto, only when writing to a terminal, following NO_COLOR and FORCE_COLOR"
+    )]
+    color: String,
+
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "Exit with 0 even if errors matched, for report-only runs"
+    )]
+    exit_zero: bool,
+
+    #[arg(
+        last = true,
+        value_name = "COMMAND",
+        help = "Run this command, e.g. tsc --pretty, and filter its output instead of stdin"
+    )]
+    command: Vec<String>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Action {
+    /// Print a Vim errorformat that reads the output of --format quickfix
+    PrintErrorformat,
 }
 
 fn main() -> ExitCode {
     let args = Args::parse();
-    let mut lines = std::io::stdin().lines().peekable();
-    let first_line = lines.peek();
-
-    // If receive no input, then we treat it as compilation being successful
-    // and return a success exit code
-    if first_line.is_none() {
-        return ExitCode::SUCCESS;
+    if let Some(Action::PrintErrorformat) = args.action {
+        println!("{}", ERRORFORMAT);
+        return Status::Clean.into();
+    }
+    if let Err(error) = check_patterns(&args.include) {
+        eprintln!("tsef: {}", error);
+        return Status::Usage.into();
+    }
+    if let Some(pattern) = &args.pattern {
+        let continuation = args.continuation.as_deref();
+        if let Err(error) = PatternStateMachine::new(pattern, continuation, Vec::new()) {
+            eprintln!("tsef: {}", error);
+            return Status::Usage.into();
+        }
+    }
+    let registry = Registry::default();
+    if let Some(name) = &args.input_format {
+        if !registry.knows(name) {
+            let names = registry.names().join(", ");
+            eprintln!(
+                "tsef: {}, expected one of {}",
+                Error::UnknownInputFormat(name.clone()),
+                names
+            );
+            return Status::Usage.into();
+        }
     }
 
-    // We use two state machines, one to parse ANSI output and one for non-ANSI
-    // We check the first line of output to detect if we are getting ANSI input or not
-    let first_line = first_line.unwrap().as_ref().unwrap();
-    let ansi = first_line.ansi_parse().next();
-
-    // If we do get ANSI input, we extract the sequence and create our AnsiStateMachine
-    // from it, else SimpleStateMachine.
-    //
-    // We extract the ANSI sequence because we use it as a marker to figure out on which
-    // lines do we need to extract the path and check if the ouput should be logged or now.
-    // For example, when you run `tsc --pretty` and there are some errors, the output would look like:
-    //
-    // \x[[<some ansi sequence>src/features/orders/index.ts\x[[some other ansi sequence ...and the rest of the error
-    //
-    // This is the structure we base our state transitions on.
-    let mut sm: Box<dyn StateMachine> = match ansi {
-        None => Box::new(SimpleStateMachine::new(args.include)),
-        Some(ansi) => match ansi {
-            Output::TextBlock(_) => Box::new(SimpleStateMachine::new(args.include)),
-            Output::Escape(a) => Box::new(AnsiStateMachine::new(a, args.include)),
-        },
+    // We either filter whatever is piped into us, or run the given command
+    // ourselves and filter its output
+    let mut child = None;
+    let input: Box<dyn BufRead> = match args.command.split_first() {
+        None => Box::new(std::io::stdin().lock()),
+        Some((program, arguments)) => {
+            let spawned = Command::new(program)
+                .args(arguments)
+                .stdout(Stdio::piped())
+                .spawn();
+            match spawned {
+                Ok(mut spawned) => {
+                    let stdout = spawned.stdout.take().expect("Child stdout is piped");
+                    child = Some(spawned);
+                    Box::new(BufReader::new(stdout))
+                }
+                Err(error) => {
+                    eprintln!("tsef: Could not run {}: {}", program, error);
+                    return Status::ChildCrashed.into();
+                }
+            }
+        }
     };
 
-    let mut was_logged = false;
-    for line in lines {
-        let line = line.unwrap();
-
-        //Our state machine parses a line and tells us whether we should print or not
-        let (_, should_print) = sm.run(&line);
-        if sm.is_finished() && !args.show_full {
-            break;
-        }
-        if should_print {
-            println!("{}", line);
-            if !sm.is_finished() {
-                was_logged = should_print;
+    let options = Options {
+        include: args.include,
+        codes: args.codes,
+        exclude_codes: args.exclude_codes,
+        show_full: args.show_full,
+        max_errors: args.max_errors,
+        max_per_file: args.max_per_file,
+        root: args.root,
+        output_base: args.output_base,
+        buffer: args.buffer,
+        dedupe: args.dedupe,
+        report_unused_suppressions: args.report_unused_suppressions,
+        suppressions: args.suppressions,
+        render: match args.format.as_str() {
+            "quickfix" => Some(Render::Quickfix),
+            _ => args.render.as_deref().and_then(Render::parse),
+        },
+        report: Report::parse(&args.format),
+        reports: args.reports,
+        pattern: args.pattern,
+        continuation: args.continuation,
+        no_color: !use_color(&args.color),
+        input_format: args.input_format.or_else(|| input_format(&args.command)),
+    };
+    let stdout = BufWriter::new(std::io::stdout().lock());
+    let mut status = match tsef::filter_with(input, stdout, &options, &registry) {
+        Ok(outcome) => outcome.verdict,
+        Err(error) => {
+            eprintln!("tsef: {}", error);
+            match error {
+                Error::InvalidPattern(_)
+                | Error::UnknownInputFormat(_)
+                | Error::InvalidSuppressions(_)
+                | Error::InvalidReportPath(_) => Status::Usage,
+                Error::Io(_) | Error::Parse { .. } => Status::Parse,
             }
         }
+    };
+    if let Some(child) = child {
+        if crashed(child) && status != Status::Parse {
+            status = Status::ChildCrashed;
+        }
     }
 
-    if was_logged {
-        return ExitCode::FAILURE;
+    if status == Status::Errors && args.exit_zero {
+        status = Status::Clean;
     }
+    return status.into();
+}
+
+// Plain tsgo output or the Angular CLI's can't always be told apart from tsc's
+// by its first line, but when we are the ones running them we know what to expect
+fn input_format(command: &[String]) -> Option<String> {
+    let program = Path::new(command.first()?).file_stem()?.to_str()?;
+    return match program {
+        "tsgo" => Some("tsgo".to_string()),
+        "ng" | "ngc" => Some("angular".to_string()),
+        _ => None,
+    };
+}
+
+// FORCE_COLOR and NO_COLOR only matter when we are left to decide. Either counts when
+// present and not empty, regardless of its value, see https://force-color.org and
+// https://no-color.org
+fn use_color(when: &str) -> bool {
+    let set = |name: &str| std::env::var_os(name).is_some_and(|value| !value.is_empty());
+    return match when {
+        "always" => true,
+        "never" => false,
+        _ if set("FORCE_COLOR") => true,
+        _ if set("NO_COLOR") => false,
+        _ => std::io::stdout().is_terminal(),
+    };
+}
 
-    return ExitCode::SUCCESS;
+// Reports given with --report look like html=report.html
+fn parse_report(value: &str) -> Result<(Report, String), String> {
+    let Some((name, path)) = value.split_once('=') else {
+        return Err("expected FORMAT=PATH, like html=report.html".to_string());
+    };
+    let Some(report) = Report::parse(name) else {
+        return Err(format!(
+            "unknown report format \"{}\", expected one of {}",
+            name,
+            Report::NAMES.join(", ")
+        ));
+    };
+    if path.is_empty() {
+        return Err(format!("no path given for the {} report", name));
+    }
+    return Ok((report, path.to_string()));
+}
+
+// tsc exits with 0, 1 or 2 once it is done type checking, anything else
+// (or being killed by a signal) means it never got to finish
+fn crashed(mut child: Child) -> bool {
+    return match child.wait() {
+        Ok(status) => !matches!(status.code(), Some(0..=2)),
+        Err(_) => true,
+    };
 }
@@ -0,0 +1,153 @@
+use std::io::Write;
+
+use crate::{Diagnostic, Outcome, Severity, limits::group_digits, report::by_file};
+
+/// Writes a Markdown report for pull request comments and job summaries: the counts
+/// up top, then a collapsed section per file with a table of its diagnostics.
+///
+/// Reports stay under `limit` bytes. Once the next row wouldn't fit, the tables end
+/// there and a last line tells how many diagnostics were left out of the report.
+pub fn write<W: Write>(
+    diagnostics: &[Diagnostic],
+    outcome: &Outcome,
+    limit: usize,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    let files = by_file(diagnostics);
+    let mut report = header(diagnostics, files.len(), outcome);
+
+    // Room for closing the section we are in and telling what got left out
+    let limit = limit.saturating_sub(256);
+    let mut written = 0;
+    'files: for (path, diagnostics) in &files {
+        let section = format!(
+            "<details>\n<summary><code>{}</code> ({})</summary>\n\n\
+             | Line | Code | Message |\n| ---: | ---- | ------- |\n",
+            escape(if path.is_empty() { "(no file)" } else { path }),
+            group_digits(diagnostics.len())
+        );
+        if report.len() + section.len() > limit {
+            break;
+        }
+        report.push_str(&section);
+        for diagnostic in diagnostics {
+            let row = row(diagnostic);
+            if report.len() + row.len() > limit {
+                report.push_str("\n</details>\n\n");
+                break 'files;
+            }
+            report.push_str(&row);
+            written += 1;
+        }
+        report.push_str("\n</details>\n\n");
+    }
+
+    let left_out = diagnostics.len() - written;
+    if left_out > 0 {
+        report.push_str(&format!(
+            "_…and {} more not shown, the report got too long._\n",
+            group_digits(left_out)
+        ));
+    }
+    write!(writer, "{}", report.trim_end())?;
+    writeln!(writer)?;
+    return Ok(());
+}
+
+// The title, then how many there were to begin with
+fn header(diagnostics: &[Diagnostic], files: usize, outcome: &Outcome) -> String {
+    let mut header = format!("### {}\n\n", title(diagnostics, files));
+    if let Some(counts) = counts(outcome) {
+        header.push_str(&format!("{}\n\n", counts));
+    }
+    return header;
+}
+
+/// Like "3 errors and 1 warning in 2 files".
+pub(crate) fn title(diagnostics: &[Diagnostic], files: usize) -> String {
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    let warnings = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Warning)
+        .count();
+    let others = diagnostics.len() - errors - warnings;
+
+    let mut counts = Vec::new();
+    if errors > 0 || diagnostics.is_empty() {
+        counts.push(count(errors, "error"));
+    }
+    if warnings > 0 {
+        counts.push(count(warnings, "warning"));
+    }
+    if others > 0 {
+        counts.push(count(others, "message"));
+    }
+    let mut title = counts.join(" and ");
+    if files > 0 {
+        title.push_str(&format!(" in {}", count(files, "file")));
+    }
+    return title;
+}
+
+/// Like "40 diagnostics found, 12 hidden by filter.", if anything got left out.
+pub(crate) fn counts(outcome: &Outcome) -> Option<String> {
+    let mut notes = Vec::new();
+    let filtered = outcome.total - outcome.matched - outcome.duplicates - outcome.suppressed;
+    if filtered > 0 {
+        notes.push(format!("{} hidden by filter", group_digits(filtered)));
+    }
+    if outcome.suppressed > 0 {
+        notes.push(format!("{} suppressed", group_digits(outcome.suppressed)));
+    }
+    if outcome.duplicates > 0 {
+        notes.push(format!(
+            "{} removed as duplicates",
+            group_digits(outcome.duplicates)
+        ));
+    }
+    if outcome.hidden > 0 {
+        notes.push(format!(
+            "{} more left out by the limits",
+            group_digits(outcome.hidden)
+        ));
+    }
+    if notes.is_empty() {
+        return None;
+    }
+    return Some(format!(
+        "{} found, {}.",
+        count(outcome.total, "diagnostic"),
+        notes.join(", ")
+    ));
+}
+
+fn row(diagnostic: &Diagnostic) -> String {
+    let position = match diagnostic.column {
+        0 => diagnostic.line.to_string(),
+        column => format!("{}:{}", diagnostic.line, column),
+    };
+    let code = match &diagnostic.code {
+        Some(code) => format!("`{}`", code),
+        None => String::new(),
+    };
+    let message = escape(&diagnostic.message).replace('\n', "<br>");
+    return format!("| {} | {} | {} |\n", position, code, message);
+}
+
+fn count(number: usize, noun: &str) -> String {
+    let plural = if number == 1 { "" } else { "s" };
+    return format!("{} {}{}", group_digits(number), noun, plural);
+}
+
+// Messages are full of types like `Array<string>` and unions, which would read as HTML
+// tags and break the table apart
+fn escape(text: &str) -> String {
+    return text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('|', "\\|");
+}
@@ -0,0 +1,226 @@
+use std::{collections::HashSet, io::Write};
+
+use crate::{
+    Diagnostic, Error, Options, Outcome, Status,
+    limits::{Limits, group_digits},
+    path_matches,
+    paths::PathNormalizer,
+    suppression_file::{self, SuppressionFile},
+    suppressions::{Suppression, Suppressions},
+};
+
+/// Decides which diagnostics make it through the filters, keeping count on the way.
+pub struct Matcher {
+    include: Vec<String>,
+    codes: Vec<String>,
+    exclude_codes: Vec<String>,
+    normalizer: PathNormalizer,
+    limits: Limits,
+    total: usize,
+    matched: usize,
+    // Set when deduplicating, holding what we kept so far
+    seen: Option<HashSet<Diagnostic>>,
+    duplicates: usize,
+    suppressions: Suppressions,
+    suppression_file: Option<SuppressionFile>,
+    suppressed: usize,
+}
+
+impl Matcher {
+    pub fn new(options: &Options) -> Result<Self, Error> {
+        let cwd = std::env::current_dir()?;
+        let normalizer = PathNormalizer::new(&cwd, options.root.as_deref());
+        return Ok(Self {
+            include: options.include.clone(),
+            codes: options.codes.clone(),
+            exclude_codes: options.exclude_codes.clone(),
+            suppression_file: load_suppression_file(options, &normalizer)?,
+            normalizer,
+            limits: Limits::new(options.max_errors, options.max_per_file),
+            total: 0,
+            matched: 0,
+            seen: options.dedupe.then(HashSet::new),
+            duplicates: 0,
+            suppressions: Suppressions::default(),
+            suppressed: 0,
+        });
+    }
+
+    pub fn normalizer(&self) -> &PathNormalizer {
+        return &self.normalizer;
+    }
+
+    /// Records a diagnostic and tells whether it is kept, which it is when it
+    /// matches the filters, isn't suppressed by the suppressions file or a `tsef-ignore`
+    /// comment, isn't the
+    /// same as one kept before when deduplicating, and still fits within the limits.
+    pub fn admit(&mut self, diagnostic: &Diagnostic) -> bool {
+        let path = self.normalizer.normalize(&diagnostic.path);
+        // Without a location, there is nothing to match the patterns against
+        let path_matches = match diagnostic.path.is_empty() {
+            true => self.include.is_empty(),
+            false => path_matches(&self.include, &path),
+        };
+        let matches = path_matches && self.code_matches(diagnostic.code.as_deref());
+
+        self.total += 1;
+        if !matches {
+            return false;
+        }
+        if let Some(file) = &mut self.suppression_file {
+            if file.suppress(&path, diagnostic) {
+                self.suppressed += 1;
+                return false;
+            }
+        }
+        if !diagnostic.path.is_empty() {
+            let source = self.normalizer.absolute(&diagnostic.path);
+            if self.suppressions.suppress(&source, diagnostic) {
+                self.suppressed += 1;
+                return false;
+            }
+        }
+        if let Some(seen) = &mut self.seen {
+            let key = Diagnostic {
+                path: path.clone(),
+                ..diagnostic.clone()
+            };
+            if !seen.insert(key) {
+                self.duplicates += 1;
+                return false;
+            }
+        }
+        self.matched += 1;
+        return self.limits.admit(&path);
+    }
+
+    fn code_matches(&self, code: Option<&str>) -> bool {
+        let listed = |codes: &Vec<String>| code.is_some_and(|code| codes.iter().any(|c| c == code));
+        return (self.codes.is_empty() || listed(&self.codes)) && !listed(&self.exclude_codes);
+    }
+
+    /// Something like `…and 19,950 more errors in 812 files`, if the limits left any out,
+    /// followed by how many duplicates were left out.
+    pub fn summary(&self) -> Option<String> {
+        let duplicates = match self.duplicates {
+            0 => None,
+            1 => Some("Removed 1 duplicate error".to_string()),
+            count => Some(format!("Removed {} duplicate errors", group_digits(count))),
+        };
+        let lines: Vec<String> = [self.limits.summary(), duplicates]
+            .into_iter()
+            .flatten()
+            .collect();
+        return (!lines.is_empty()).then(|| lines.join("\n"));
+    }
+
+    /// The `tsef-ignore` comments that didn't suppress anything, with their paths
+    /// normalized. See `Suppressions::unused` for which files get looked into.
+    pub fn unused_suppressions(&mut self) -> Vec<(String, Suppression)> {
+        let unused = self
+            .suppressions
+            .unused(self.normalizer.root(), &self.include);
+        return unused
+            .into_iter()
+            .map(|(path, suppression)| (self.normalizer.normalize(&path), suppression))
+            .collect();
+    }
+
+    /// The suppressions file, if there is one.
+    pub fn suppression_file(&self) -> Option<&SuppressionFile> {
+        return self.suppression_file.as_ref();
+    }
+
+    /// Writes out the expired entries of the suppressions file, and with
+    /// `report_unused_suppressions` the suppressions that didn't suppress anything,
+    /// telling whether there were any. With `aside`, they go to stderr instead, for when
+    /// `writer` carries something they would break, like a report.
+    pub fn write_stale_suppressions<W: Write>(
+        &mut self,
+        options: &Options,
+        writer: &mut W,
+        aside: bool,
+    ) -> Result<bool, Error> {
+        let mut lines = Vec::new();
+        if let Some(file) = &self.suppression_file {
+            for entry in file.expired() {
+                lines.push(format!(
+                    "{}:{}: Suppression of {} in {} expired on {}, owned by {}: {}",
+                    file.path,
+                    entry.line,
+                    entry.code,
+                    entry.path,
+                    entry.expires,
+                    entry.owner,
+                    entry.reason
+                ));
+            }
+        }
+        if options.report_unused_suppressions {
+            for (path, suppression) in self.unused_suppressions() {
+                lines.push(format!(
+                    "{}:{}: Unused suppression: {}",
+                    path, suppression.line, suppression.text
+                ));
+            }
+            if let Some(file) = &self.suppression_file {
+                for entry in file.unused() {
+                    lines.push(format!(
+                        "{}:{}: Unused suppression of {} in {}, owned by {}: {}",
+                        file.path, entry.line, entry.code, entry.path, entry.owner, entry.reason
+                    ));
+                }
+            }
+        }
+
+        for line in &lines {
+            match aside {
+                true => writeln!(std::io::stderr(), "{}", line)?,
+                false => writeln!(writer, "{}", line)?,
+            }
+        }
+        return Ok(!lines.is_empty());
+    }
+
+    /// Whether any diagnostic was kept so far, whether it got shown or the limits left it out.
+    pub fn kept(&self) -> bool {
+        return self.limits.shown() + self.limits.hidden() > 0;
+    }
+
+    /// Sums up the counts, `stale` telling whether there were stale suppressions, which
+    /// fail the run just like kept diagnostics do.
+    pub fn outcome(&self, stale: bool) -> Outcome {
+        let verdict = match stale || self.kept() {
+            true => Status::Errors,
+            false => Status::Clean,
+        };
+        return Outcome {
+            verdict,
+            total: self.total,
+            matched: self.matched,
+            duplicates: self.duplicates,
+            suppressed: self.suppressed,
+            shown: self.limits.shown(),
+            hidden: self.limits.hidden(),
+        };
+    }
+}
+
+// The file we were given has to be there, the one under the root only if it is
+fn load_suppression_file(
+    options: &Options,
+    normalizer: &PathNormalizer,
+) -> Result<Option<SuppressionFile>, Error> {
+    if let Some(path) = &options.suppressions {
+        return Ok(Some(SuppressionFile::load(path, path)?));
+    }
+    let path = std::path::Path::new(normalizer.root()).join(suppression_file::FILE_NAME);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let path = path.to_string_lossy().to_string();
+    return Ok(Some(SuppressionFile::load(
+        &path,
+        &normalizer.normalize(&path),
+    )?));
+}
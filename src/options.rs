@@ -0,0 +1,49 @@
+use crate::{render::Render, report::Report};
+
+/// Everything that decides which lines make it through the filter and how.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    /// Glob patterns the path of a diagnostic has to match, all of them if empty
+    pub include: Vec<String>,
+    /// Codes a diagnostic has to have one of, like `TS2322` or an ESLint rule, any if empty
+    pub codes: Vec<String>,
+    /// Codes of diagnostics that are left out
+    pub exclude_codes: Vec<String>,
+    /// Print the compiler's summary after the diagnostics as well
+    pub show_full: bool,
+    /// Stop printing after this many diagnostics, only counting the rest
+    pub max_errors: Option<usize>,
+    /// Print at most this many diagnostics per file, only counting the rest
+    pub max_per_file: Option<usize>,
+    /// Directory the include patterns are relative to, the current one if unset
+    pub root: Option<String>,
+    /// Directory the printed paths get rewritten to be relative to
+    pub output_base: Option<String>,
+    /// Collect everything and print it sorted by file at the end, instead of
+    /// printing each diagnostic as soon as we know it is kept
+    pub buffer: bool,
+    /// Regular expression for the lines starting a diagnostic, read instead of
+    /// any known format. See `PatternStateMachine` for the groups it can name
+    pub pattern: Option<String>,
+    /// Regular expression for the lines that continue a diagnostic when using `pattern`,
+    /// indented lines if unset
+    pub continuation: Option<String>,
+    /// Leave out diagnostics with the same path, position, code and message as one kept before
+    pub dedupe: bool,
+    /// Write out the `tsef-ignore` comments and entries of the suppressions file that
+    /// didn't suppress anything, failing if there are any
+    pub report_unused_suppressions: bool,
+    /// File listing reviewed suppressions, `tsef-suppressions.toml` under the root if unset
+    /// and there is one. See `SuppressionFile`
+    pub suppressions: Option<String>,
+    /// Write kept diagnostics again in this style, instead of the way they came in
+    pub render: Option<Render>,
+    /// Write a report about the kept diagnostics once the input is over, instead of any lines
+    pub report: Option<Report>,
+    /// Reports written to these files once the input is over, along with the usual output
+    pub reports: Vec<(Report, String)>,
+    /// Strip ANSI escape sequences from everything written, leaving the text as it was laid out
+    pub no_color: bool,
+    /// Name of the tool whose output we read, like `tsgo`, detected from the output if unset
+    pub input_format: Option<String>,
+}
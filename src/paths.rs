@@ -0,0 +1,156 @@
+use std::path::Path;
+
+/// Brings the paths printed by the compiler into one shape before we match them
+/// against the include patterns. tsc prints paths relative to where it runs, but
+/// with `--build`, monorepo tools or logs coming from Windows we also get absolute
+/// paths, backslashes, `..` segments and `./` prefixes. Deno prints `file://` URLs.
+#[derive(Clone, Debug)]
+pub struct PathNormalizer {
+    cwd: String,
+    root: String,
+}
+
+impl PathNormalizer {
+    /// Paths get resolved against `cwd` and then made relative to `root`,
+    /// which is itself resolved against `cwd` when relative.
+    pub fn new(cwd: &Path, root: Option<&str>) -> Self {
+        let cwd = collapse(&cwd.to_string_lossy());
+        let root = match root {
+            Some(root) => collapse(&join(&cwd, root)),
+            None => cwd.clone(),
+        };
+        return Self { cwd, root };
+    }
+
+    /// The directory normalized paths are relative to, as an absolute path.
+    pub fn root(&self) -> &str {
+        return &self.root;
+    }
+
+    /// Turns a path as printed by the compiler into a path relative to the root,
+    /// using forward slashes. Paths outside of the root stay absolute.
+    pub fn normalize(&self, path: &str) -> String {
+        let absolute = self.absolute(path);
+        return match strip_dir(&absolute, &self.root) {
+            Some(relative) => relative.to_string(),
+            None => absolute,
+        };
+    }
+
+    /// Rewrites a path as printed by the compiler to be relative to `base`,
+    /// going up with `..` where needed.
+    pub fn relative_to(&self, path: &str, base: &str) -> String {
+        let path = self.absolute(path);
+        let base = collapse(&join(&self.cwd, base));
+        if prefix(&path) != prefix(&base) {
+            return path;
+        }
+
+        let path_segments: Vec<&str> = segments(&path).collect();
+        let base_segments: Vec<&str> = segments(&base).collect();
+        let common = path_segments
+            .iter()
+            .zip(base_segments.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let mut relative = vec![".."; base_segments.len() - common];
+        relative.extend(&path_segments[common..]);
+        return relative.join("/");
+    }
+
+    /// Resolves a path as printed by the compiler, or a `file://` URL, to an absolute path.
+    pub fn absolute(&self, path: &str) -> String {
+        let path = from_file_url(path).unwrap_or_else(|| path.to_string());
+        return collapse(&join(&self.cwd, &path));
+    }
+}
+
+/// Whether a path is absolute, be it a unix path or one coming from Windows.
+pub fn is_absolute(path: &str) -> bool {
+    return !prefix(&path.replace('\\', "/")).is_empty();
+}
+
+/// Turns a URL like `file:///home/me/main.ts` or `file:///C:/project/main.ts`
+/// into the path it points to, decoding escapes like `%20` on the way.
+pub fn from_file_url(url: &str) -> Option<String> {
+    let path = url.strip_prefix("file://")?;
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = path
+            .get(index + 1..index + 3)
+            .filter(|_| bytes[index] == b'%')
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+
+    let path = String::from_utf8_lossy(&decoded).into_owned();
+    // Windows paths come with a slash before the drive letter
+    return match path.strip_prefix('/') {
+        Some(rest) if !prefix(rest).is_empty() => Some(rest.to_string()),
+        _ => Some(path),
+    };
+}
+
+fn join(dir: &str, path: &str) -> String {
+    let path = path.replace('\\', "/");
+    if prefix(&path).is_empty() {
+        return format!("{}/{}", dir, path);
+    }
+    return path;
+}
+
+// The part of an absolute path that comes before its segments, which is
+// `/` on unix and something like `C:/` for paths coming from Windows
+fn prefix(path: &str) -> &str {
+    if path.starts_with('/') {
+        return "/";
+    }
+    let bytes = path.as_bytes();
+    if bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'/' {
+        return &path[..3];
+    }
+    return "";
+}
+
+fn segments(path: &str) -> impl Iterator<Item = &str> {
+    return path[prefix(path).len()..]
+        .split('/')
+        .filter(|segment| !segment.is_empty());
+}
+
+// Lexically resolves `.` and `..` segments, without looking at the file system
+fn collapse(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let mut collapsed: Vec<&str> = Vec::new();
+    for segment in segments(&path) {
+        match segment {
+            "." => {}
+            ".." if collapsed.last().is_some_and(|last| *last != "..") => {
+                collapsed.pop();
+            }
+            ".." if !prefix(&path).is_empty() => {}
+            _ => collapsed.push(segment),
+        }
+    }
+    return format!("{}{}", prefix(&path).to_uppercase(), collapsed.join("/"));
+}
+
+fn strip_dir<'a>(path: &'a str, dir: &str) -> Option<&'a str> {
+    let rest = path.strip_prefix(dir)?;
+    if dir.ends_with('/') {
+        return Some(rest);
+    }
+    return rest.strip_prefix('/');
+}
@@ -0,0 +1,103 @@
+use regex::{Captures, Regex};
+
+use crate::{Diagnostic, Error, Result, Severity, State, StateMachine, diagnostic::strip_ansi};
+
+/// Reads the output of any tool with a regular expression for the lines that start
+/// a diagnostic, for when writing a `DiagnosticParser` is more than it's worth.
+///
+/// The expression names its groups after what they capture: `file` is required,
+/// `line`, `col` (or `column`), `severity`, `code` and `message` are optional:
+///
+/// `^(?P<file>[^:]+):(?P<line>\d+):(?P<col>\d+): (?P<severity>\w+) (?P<code>\S+): (?P<message>.*)$`
+///
+/// Lines following a diagnostic belong to it as long as they match the continuation
+/// rule, which by default takes indented lines, like tsc does for long messages.
+pub struct PatternStateMachine {
+    pub state: State,
+    pub include: Vec<String>,
+    header: Regex,
+    continuation: Regex,
+    diagnostic: Option<Diagnostic>,
+    started: bool,
+}
+
+/// The continuation rule used when none is given: lines that are indented and not blank.
+pub const DEFAULT_CONTINUATION: &str = r"^\s+\S";
+
+impl PatternStateMachine {
+    pub fn new(pattern: &str, continuation: Option<&str>, include: Vec<String>) -> Result<Self> {
+        let header = compile(pattern)?;
+        if !header.capture_names().any(|name| name == Some("file")) {
+            return Err(Error::InvalidPattern(format!(
+                "The pattern \"{}\" has no (?P<file>...) group",
+                pattern
+            )));
+        }
+        let continuation = compile(continuation.unwrap_or(DEFAULT_CONTINUATION))?;
+        return Ok(Self {
+            state: State::ParseToPause,
+            include,
+            header,
+            continuation,
+            diagnostic: None,
+            started: false,
+        });
+    }
+
+    fn parse(&self, line: &str) -> Option<Diagnostic> {
+        let captures = self.header.captures(line)?;
+        let group = |name: &str| captures.name(name).map(|m| m.as_str());
+        let number = |captures: &Captures, names: &[&str]| {
+            let value = names.iter().find_map(|name| captures.name(name))?;
+            return value.as_str().parse().ok();
+        };
+
+        let path = group("file").filter(|path| !path.is_empty())?;
+        let row = number(&captures, &["line"]).unwrap_or(0);
+        let column = number(&captures, &["col", "column"]).unwrap_or(0);
+        let mut diagnostic = Diagnostic::new(path, row, column);
+        if let Some(severity) = group("severity").and_then(Severity::parse) {
+            diagnostic.severity = severity;
+        }
+        diagnostic.code = group("code").map(|code| code.to_string());
+        diagnostic.message = group("message").unwrap_or_default().to_string();
+        return Some(diagnostic);
+    }
+}
+
+fn compile(pattern: &str) -> Result<Regex> {
+    return Regex::new(pattern).map_err(|error| {
+        Error::InvalidPattern(format!(
+            "Invalid regular expression \"{}\": {}",
+            pattern, error
+        ))
+    });
+}
+
+impl StateMachine for PatternStateMachine {
+    fn run(&mut self, line: &String) -> (&State, bool) {
+        self.started = false;
+        let line = strip_ansi(line);
+        if let Some(diagnostic) = self.parse(&line) {
+            self.diagnostic = Some(diagnostic);
+            self.started = true;
+        } else if !self.continuation.is_match(&line) {
+            self.diagnostic = None;
+        }
+
+        // Lines that aren't part of any diagnostic only make it through when we aren't filtering anything
+        return (&self.state, self.include.is_empty());
+    }
+
+    fn is_finished(&self) -> bool {
+        false
+    }
+
+    fn diagnostic(&self) -> Option<&Diagnostic> {
+        self.diagnostic.as_ref()
+    }
+
+    fn starts_diagnostic(&self) -> bool {
+        self.started
+    }
+}
@@ -0,0 +1,338 @@
+use std::io::Write;
+
+use crate::{
+    Diagnostic, Error, Options, Outcome, StateMachine,
+    diagnostic::strip_ansi,
+    matcher::Matcher,
+    render::{Render, Renderer, message_chain},
+    report::{self, Context},
+    tsgo_state_machine::is_summary,
+};
+
+/// Feeds lines through a state machine and writes out the ones that make it through.
+///
+/// By default every line is written as soon as we know it is kept, and the writer gets
+/// flushed as soon as a diagnostic is complete, so that output shows up in bounded time
+/// even in watch mode or with a slow compiler. With `Options::buffer`, everything is
+/// collected instead and written sorted by file once the input is over.
+///
+/// With `Options::render`, kept diagnostics are collected until complete and written
+/// again in the style asked for, instead of the way they came in. With `Options::report`,
+/// they are only collected, to be written as a report once the input is over, and with
+/// `Options::reports` they are collected for the report files along with being written.
+pub struct Pipeline<W: Write> {
+    sm: Box<dyn StateMachine>,
+    writer: W,
+    options: Options,
+    matcher: Matcher,
+    keep: bool,
+    line_number: usize,
+    leading: Vec<String>,
+    blocks: Vec<Block>,
+    trailing: Vec<String>,
+    held: Vec<String>,
+    heading: Vec<String>,
+    heading_complete: bool,
+    renderer: Option<Renderer>,
+    rendering: Option<(Diagnostic, Vec<String>)>,
+    reporting: Option<(Diagnostic, Vec<String>)>,
+    reported: Vec<Diagnostic>,
+}
+
+// A kept diagnostic along with every line that belongs to it
+struct Block {
+    path: String,
+    line: usize,
+    column: usize,
+    lines: Vec<String>,
+}
+
+impl<W: Write> Pipeline<W> {
+    pub fn new(sm: Box<dyn StateMachine>, writer: W, options: &Options) -> Result<Self, Error> {
+        return Ok(Self {
+            sm,
+            writer,
+            options: options.clone(),
+            matcher: Matcher::new(options)?,
+            keep: true,
+            line_number: 0,
+            leading: Vec::new(),
+            blocks: Vec::new(),
+            trailing: Vec::new(),
+            held: Vec::new(),
+            heading: Vec::new(),
+            heading_complete: false,
+            renderer: options.render.map(Renderer::new),
+            rendering: None,
+            reporting: None,
+            reported: Vec::new(),
+        });
+    }
+
+    pub fn push(&mut self, line: &str) -> Result<(), Error> {
+        self.line_number += 1;
+        let line = line.to_string();
+
+        //Our state machine parses a line and tells us whether we should print or not
+        let (_, should_print) = self.sm.run(&line);
+        if let Some(message) = self.sm.parse_error() {
+            return Err(Error::Parse {
+                line: self.line_number,
+                message: message.to_string(),
+            });
+        }
+
+        // Anything but the next line of the same diagnostic means the previous one is complete
+        let continues_diagnostic = self.sm.diagnostic().is_some() && !self.sm.starts_diagnostic();
+        if !continues_diagnostic {
+            self.render()?;
+        }
+        if !continues_diagnostic && !self.options.buffer {
+            self.writer.flush()?;
+        }
+
+        // A diagnostic we held back may get followed by something else before telling where it is
+        let replaced = self.sm.diagnostic().is_none() || self.sm.starts_diagnostic();
+        if !self.held.is_empty() && replaced {
+            self.release(&Diagnostic::new("", 0, 0), None)?;
+        }
+
+        // Headings only show up along with the first kept diagnostic under them
+        if self.sm.is_heading() {
+            if self.heading_complete {
+                self.heading.clear();
+                self.heading_complete = false;
+            }
+            self.heading.push(line);
+            return Ok(());
+        }
+        self.heading_complete = true;
+
+        if self.sm.is_finished() && !self.options.show_full {
+            return Ok(());
+        }
+        if self.sm.is_finished() {
+            for line in std::mem::take(&mut self.heading) {
+                self.emit(line, false)?;
+            }
+        }
+
+        // Lines belonging to a diagnostic are kept or dropped all together, so we
+        // decide once we know where it is and stick to it until the next one starts.
+        // Most tools tell on the first line, for the others we hold lines back until then.
+        // The state machine only has the last word on lines outside of diagnostics.
+        let Some(diagnostic) = self.sm.diagnostic() else {
+            // The tool's own summary goes along with what it sums up, unless asked for
+            let lone_summary = is_summary(&line) && !self.matcher.kept() && !self.options.show_full;
+            if should_print && !lone_summary {
+                self.emit(line, false)?;
+            }
+            return Ok(());
+        };
+        if self.sm.awaits_location() {
+            self.held.push(line);
+            return Ok(());
+        }
+        if self.sm.starts_diagnostic() || !self.held.is_empty() {
+            let diagnostic = diagnostic.clone();
+            return self.release(&diagnostic, Some(line));
+        }
+        if self.keep {
+            if let Some((_, lines)) = &mut self.reporting {
+                lines.push(line.clone());
+            }
+            self.emit(line, true)?;
+        }
+        return Ok(());
+    }
+
+    // Decides whether to keep a diagnostic now that we know where it is, and writes out
+    // its heading and the lines held back so far along with the one that told us
+    fn release(&mut self, diagnostic: &Diagnostic, line: Option<String>) -> Result<(), Error> {
+        self.render()?;
+        self.keep = self.matcher.admit(diagnostic);
+        if !self.keep {
+            self.held.clear();
+            return Ok(());
+        }
+
+        if self.options.report.is_some() || !self.options.reports.is_empty() {
+            let mut lines = self.held.clone();
+            lines.extend(line.clone());
+            self.reporting = Some((diagnostic.clone(), lines));
+        }
+
+        // Rendered and reported diagnostics tell their path themselves, so they go without headings
+        let mut lines = std::mem::take(&mut self.heading);
+        if self.renderer.is_some() || self.options.report.is_some() {
+            lines.clear();
+        }
+        if self.renderer.is_some() {
+            self.rendering = Some((diagnostic.clone(), Vec::new()));
+        }
+        lines.append(&mut self.held);
+        lines.extend(line);
+        if let Some(base) = &self.options.output_base {
+            let rewritten = self
+                .matcher
+                .normalizer()
+                .relative_to(&diagnostic.path, base);
+            for line in &mut lines {
+                *line = line.replacen(&diagnostic.path, &rewritten, 1);
+            }
+        }
+
+        if self.options.buffer {
+            self.blocks.push(Block {
+                path: self.matcher.normalizer().normalize(&diagnostic.path),
+                line: diagnostic.line,
+                column: diagnostic.column,
+                lines: Vec::new(),
+            });
+        }
+        for line in lines {
+            self.emit(line, true)?;
+        }
+        return Ok(());
+    }
+
+    fn emit(&mut self, line: String, in_diagnostic: bool) -> Result<(), Error> {
+        if self.options.report.is_some() {
+            return Ok(());
+        }
+        let line = match (&mut self.rendering, &self.renderer) {
+            (Some((_, lines)), _) if in_diagnostic => {
+                lines.push(line);
+                return Ok(());
+            }
+            (_, Some(renderer)) if !in_diagnostic => match renderer.render_line(line) {
+                Some(line) => line,
+                None => return Ok(()),
+            },
+            _ => line,
+        };
+        let line = match self.options.no_color {
+            true => strip_ansi(&line),
+            false => line,
+        };
+
+        if !self.options.buffer {
+            writeln!(self.writer, "{}", line)?;
+        } else if in_diagnostic {
+            self.blocks
+                .last_mut()
+                .expect("Kept diagnostics start a block")
+                .lines
+                .push(line);
+        } else if self.blocks.is_empty() {
+            self.leading.push(line);
+        } else {
+            self.trailing.push(line);
+        }
+        return Ok(());
+    }
+
+    // Adds the diagnostic being collected to the reports and writes it out rendered,
+    // now that it is complete
+    fn render(&mut self) -> Result<(), Error> {
+        let normalizer = self.matcher.normalizer();
+        if let Some((mut diagnostic, lines)) = self.reporting.take() {
+            diagnostic.path = report::path(normalizer, &diagnostic.path, &self.options);
+            for line in message_chain(&lines) {
+                if !diagnostic.message.is_empty() {
+                    diagnostic.message.push('\n');
+                }
+                diagnostic.message.push_str(line.trim());
+            }
+            self.reported.push(diagnostic);
+        }
+        let (Some((diagnostic, lines)), Some(renderer)) =
+            (self.rendering.take(), self.renderer.as_mut())
+        else {
+            return Ok(());
+        };
+        let path = match &self.options.output_base {
+            Some(base) if !diagnostic.path.is_empty() => {
+                normalizer.relative_to(&diagnostic.path, base)
+            }
+            // Editors can't open the `file://` URLs Deno prints
+            _ if diagnostic.path.starts_with("file://") => normalizer.absolute(&diagnostic.path),
+            _ => diagnostic.path.clone(),
+        };
+        let source = normalizer.absolute(&diagnostic.path);
+        for line in renderer.render(&diagnostic, &lines, &path, &source) {
+            self.emit(line, true)?;
+        }
+        return Ok(());
+    }
+
+    pub fn finish(mut self) -> Result<Outcome, Error> {
+        if !self.held.is_empty() {
+            self.release(&Diagnostic::new("", 0, 0), None)?;
+        }
+        self.render()?;
+        if self.options.buffer {
+            self.write_buffered()?;
+        }
+        // Expired suppressions, and unused ones when asked for, fail the run like errors do,
+        // so that temporary exceptions don't stay around for good
+        let aside = self.options.report.is_some();
+        let stale =
+            self.matcher
+                .write_stale_suppressions(&self.options, &mut self.writer, aside)?;
+        let outcome = self.matcher.outcome(stale);
+        let context = Context::new(&self.options, &outcome, self.matcher.normalizer());
+        if let Some(format) = self.options.report {
+            report::write(format, &self.reported, &context, &mut self.writer)?;
+        }
+        report::write_files(&self.options.reports, &self.reported, &context)?;
+        // Quickfix lists and reports only hold diagnostics
+        let summary = self.matcher.summary();
+        let only_diagnostics =
+            self.options.render == Some(Render::Quickfix) || self.options.report.is_some();
+        if let (Some(summary), false) = (summary, only_diagnostics) {
+            writeln!(self.writer, "{}", summary)?;
+        }
+        self.writer.flush()?;
+        return Ok(outcome);
+    }
+
+    fn write_buffered(&mut self) -> Result<(), Error> {
+        // Diagnostics end with blank lines, and the last one with a few more before
+        // the summary. Those stay where they were, so only the contents get sorted.
+        let blank_lines: Vec<usize> = self
+            .blocks
+            .iter()
+            .map(|block| {
+                block
+                    .lines
+                    .iter()
+                    .rev()
+                    .take_while(|l| l.is_empty())
+                    .count()
+            })
+            .collect();
+        let mut order: Vec<usize> = (0..self.blocks.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (&self.blocks[a], &self.blocks[b]);
+            (&a.path, a.line, a.column).cmp(&(&b.path, b.line, b.column))
+        });
+
+        for line in &self.leading {
+            writeln!(self.writer, "{}", line)?;
+        }
+        for (position, &index) in order.iter().enumerate() {
+            let block = &self.blocks[index];
+            for line in &block.lines[..block.lines.len() - blank_lines[index]] {
+                writeln!(self.writer, "{}", line)?;
+            }
+            for _ in 0..blank_lines[position] {
+                writeln!(self.writer)?;
+            }
+        }
+        for line in &self.trailing {
+            writeln!(self.writer, "{}", line)?;
+        }
+        return Ok(());
+    }
+}
@@ -0,0 +1,201 @@
+use ansi_parser::{AnsiParser, Output};
+
+use crate::{
+    StateMachine, angular_state_machine::AngularStateMachine, ansi_state_machine::AnsiStateMachine,
+    deno_state_machine::DenoStateMachine, eslint_json, eslint_state_machine::EslintStateMachine,
+    simple_state_machine::SimpleStateMachine, svelte_check_state_machine::SvelteCheckStateMachine,
+    tsgo_state_machine::TsgoStateMachine, vue_tsc_state_machine::VueTscStateMachine,
+};
+
+/// Knows how to read the output of some tool, by handing out state machines for it.
+///
+/// Implement this to teach tsef about a tool of your own, and register it with
+/// `Registry::register` before filtering with `filter_with`:
+///
+/// ```
+/// use tsef::{DiagnosticParser, Registry, StateMachine, simple_state_machine::SimpleStateMachine};
+///
+/// struct Lint;
+///
+/// impl DiagnosticParser for Lint {
+///     fn name(&self) -> &str {
+///         "lint"
+///     }
+///
+///     fn detect(&self, first_line: &str) -> bool {
+///         first_line.starts_with("lint v")
+///     }
+///
+///     fn state_machine(&self, _first_line: &str, include: Vec<String>) -> Box<dyn StateMachine> {
+///         // Our tool prints its diagnostics just like plain tsc does
+///         Box::new(SimpleStateMachine::new(include))
+///     }
+/// }
+///
+/// let mut registry = Registry::default();
+/// registry.register(Lint);
+/// assert!(registry.get("lint").is_some());
+/// ```
+pub trait DiagnosticParser {
+    /// What the format is called, as given to `--input-format`.
+    fn name(&self) -> &str;
+
+    /// Whether the first line of some output, not counting blank lines, tells that the output
+    /// is in this format. Formats that can't tell leave this out and have to be asked for by name.
+    fn detect(&self, _first_line: &str) -> bool {
+        return false;
+    }
+
+    /// Creates a state machine to read the output with, `include` being the patterns
+    /// lines outside of diagnostics may want to know about.
+    fn state_machine(&self, first_line: &str, include: Vec<String>) -> Box<dyn StateMachine>;
+}
+
+/// The formats we can read, looked up by name or detected from the output.
+pub struct Registry {
+    parsers: Vec<Box<dyn DiagnosticParser>>,
+}
+
+impl Default for Registry {
+    /// A registry with every format tsef knows about out of the box.
+    fn default() -> Self {
+        let mut registry = Self {
+            parsers: Vec::new(),
+        };
+        // Those registered last are asked first, and tsc is what we fall back to
+        let builtins = [
+            builtin("tsc", |_| false, tsc),
+            builtin("vue-tsc", |_| false, tsc),
+            builtin("tsgo", TsgoStateMachine::detect, tsgo),
+            builtin("eslint", EslintStateMachine::detect, |_, include| {
+                Box::new(EslintStateMachine::new(include))
+            }),
+            builtin("angular", AngularStateMachine::detect, |_, include| {
+                Box::new(AngularStateMachine::new(include))
+            }),
+            builtin("deno", DenoStateMachine::detect, |_, include| {
+                Box::new(DenoStateMachine::new(include))
+            }),
+            builtin(
+                "svelte-check",
+                SvelteCheckStateMachine::detect,
+                |_, include| Box::new(SvelteCheckStateMachine::new(include)),
+            ),
+        ];
+        for parser in builtins {
+            registry.register(parser);
+        }
+        return registry;
+    }
+}
+
+impl Registry {
+    /// Adds a format, which takes precedence over the ones registered before
+    /// it, both when detecting and when looked up by a name they share.
+    pub fn register(&mut self, parser: impl DiagnosticParser + 'static) {
+        self.parsers.push(Box::new(parser));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn DiagnosticParser> {
+        return self
+            .parsers
+            .iter()
+            .rev()
+            .find(|parser| parser.name() == name)
+            .map(|parser| parser.as_ref());
+    }
+
+    /// The format the first line of some output is in, tsc if none of them can tell.
+    pub fn detect(&self, first_line: &str) -> Option<&dyn DiagnosticParser> {
+        return self
+            .parsers
+            .iter()
+            .rev()
+            .find(|parser| parser.detect(first_line))
+            .map(|parser| parser.as_ref())
+            .or_else(|| self.get("tsc"));
+    }
+
+    /// Names of the formats we can read, including ESLint's JSON, which
+    /// is read as a whole rather than by a state machine.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for parser in self.parsers.iter().rev() {
+            if !names.contains(&parser.name()) {
+                names.push(parser.name());
+            }
+        }
+        names.push(eslint_json::NAME);
+        return names;
+    }
+
+    /// Whether `name` is one of `names`.
+    pub fn knows(&self, name: &str) -> bool {
+        return name == eslint_json::NAME || self.get(name).is_some();
+    }
+}
+
+type Create = fn(&str, Vec<String>) -> Box<dyn StateMachine>;
+
+// The formats that come with tsef, which all know how to tell themselves apart
+struct Builtin {
+    name: &'static str,
+    detect: fn(&str) -> bool,
+    create: Create,
+}
+
+fn builtin(name: &'static str, detect: fn(&str) -> bool, create: Create) -> Builtin {
+    return Builtin {
+        name,
+        detect,
+        create,
+    };
+}
+
+impl DiagnosticParser for Builtin {
+    fn name(&self) -> &str {
+        return self.name;
+    }
+
+    fn detect(&self, first_line: &str) -> bool {
+        return (self.detect)(first_line);
+    }
+
+    fn state_machine(&self, first_line: &str, include: Vec<String>) -> Box<dyn StateMachine> {
+        return (self.create)(first_line, include);
+    }
+}
+
+fn tsc(first_line: &str, include: Vec<String>) -> Box<dyn StateMachine> {
+    // We use two state machines, one to parse ANSI output and one for non-ANSI
+    // We check the first line of output to detect if we are getting ANSI input or not
+    let ansi = first_line.ansi_parse().next();
+
+    // If we do get ANSI input, we extract the sequence and create our AnsiStateMachine
+    // from it, else SimpleStateMachine.
+    //
+    // We extract the ANSI sequence because we use it as a marker to figure out on which
+    // lines do we need to extract the path and check if the ouput should be logged or now.
+    // For example, when you run `tsc --pretty` and there are some errors, the output would look like:
+    //
+    // \x[[<some ansi sequence>src/features/orders/index.ts\x[[some other ansi sequence ...and the rest of the error
+    //
+    // This is the structure we base our state transitions on.
+    let sm: Box<dyn StateMachine> = match ansi {
+        None => Box::new(SimpleStateMachine::new(include)),
+        Some(ansi) => match ansi {
+            Output::TextBlock(_) => Box::new(SimpleStateMachine::new(include)),
+            Output::Escape(a) => Box::new(AnsiStateMachine::new(a, include)),
+        },
+    };
+    // Errors in Vue components come from virtual files, which we match as the component
+    return Box::new(VueTscStateMachine::new(sm));
+}
+
+// Pretty tsgo output gets its own state machine, plain tsgo output is the same as tsc's
+fn tsgo(first_line: &str, include: Vec<String>) -> Box<dyn StateMachine> {
+    return match first_line.ansi_parse().next() {
+        Some(Output::Escape(_)) => Box::new(TsgoStateMachine::new(include)),
+        _ => Box::new(SimpleStateMachine::new(include)),
+    };
+}
@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+
+use crate::{
+    Diagnostic, Severity,
+    diagnostic::{parse_location, strip_ansi},
+};
+
+/// The style diagnostics get written in again, whatever style they came in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Render {
+    /// Like `tsc --pretty`, colored and with the code the diagnostic points at
+    Pretty,
+    /// Like `tsc --pretty false`, one line per diagnostic plus its message chain
+    Plain,
+    /// One line per diagnostic and nothing else, like `path:line:col: error TS2322: message`,
+    /// the way editors read their quickfix lists
+    Quickfix,
+}
+
+impl Render {
+    pub fn parse(name: &str) -> Option<Self> {
+        return match name {
+            "pretty" => Some(Render::Pretty),
+            "plain" => Some(Render::Plain),
+            "quickfix" => Some(Render::Quickfix),
+            _ => None,
+        };
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+const CYAN: &str = "\x1b[96m";
+const YELLOW: &str = "\x1b[93m";
+const GREY: &str = "\x1b[90m";
+const INVERSE: &str = "\x1b[7m";
+
+/// Writes diagnostics the way tsc does, reading the files they point
+/// into to draw the code frame of pretty output.
+pub struct Renderer {
+    style: Render,
+    sources: HashMap<String, Option<Vec<String>>>,
+}
+
+impl Renderer {
+    pub fn new(style: Render) -> Self {
+        return Self {
+            style,
+            sources: HashMap::new(),
+        };
+    }
+
+    /// Renders a diagnostic out of the lines it came in, `path` being how to print its
+    /// path and `source` where to read the file it points into from.
+    pub fn render(
+        &mut self,
+        diagnostic: &Diagnostic,
+        lines: &[String],
+        path: &str,
+        source: &str,
+    ) -> Vec<String> {
+        let chain = message_chain(lines);
+        return match self.style {
+            Render::Plain => {
+                let mut rendered = vec![plain_header(diagnostic, path)];
+                rendered.extend(chain);
+                rendered
+            }
+            Render::Pretty => {
+                let mut rendered = vec![pretty_header(diagnostic, path)];
+                rendered.extend(chain);
+                rendered.push(String::new());
+                if let Some(frame) = self.frame(diagnostic, source) {
+                    rendered.extend(frame);
+                    rendered.push(String::new());
+                }
+                rendered
+            }
+            Render::Quickfix => {
+                let mut line = quickfix_line(diagnostic, path);
+                for text in chain {
+                    line.push(' ');
+                    line.push_str(text.trim());
+                }
+                vec![line]
+            }
+        };
+    }
+
+    /// Lines outside of diagnostics stay as they are, only losing their colors in plain
+    /// style, or get left out of quickfix lists.
+    pub fn render_line(&self, line: String) -> Option<String> {
+        return match self.style {
+            Render::Plain => Some(strip_ansi(&line)),
+            Render::Pretty => Some(line),
+            Render::Quickfix => None,
+        };
+    }
+
+    // The line the diagnostic points at, underlining what starts at its column
+    fn frame(&mut self, diagnostic: &Diagnostic, source: &str) -> Option<Vec<String>> {
+        if diagnostic.line == 0 {
+            return None;
+        }
+        let lines = self
+            .sources
+            .entry(source.to_string())
+            .or_insert_with(|| {
+                let text = std::fs::read_to_string(source).ok()?;
+                return Some(text.lines().map(|line| line.to_string()).collect());
+            })
+            .as_ref()?;
+        let text = lines.get(diagnostic.line - 1)?;
+
+        let number = diagnostic.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let start = diagnostic.column.saturating_sub(1);
+        // Tabs are kept so that the underline lines up with the code above it
+        let indent: String = text
+            .chars()
+            .take(start)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let width = token_width(&text.chars().skip(start).collect::<String>());
+        let color = severity_color(diagnostic.severity);
+        return Some(vec![
+            format!("{}{}{} {}", INVERSE, number, RESET, text),
+            format!(
+                "{}{}{} {}{}{}{}",
+                INVERSE,
+                gutter,
+                RESET,
+                indent,
+                color,
+                "~".repeat(width),
+                RESET
+            ),
+        ]);
+    }
+}
+
+fn plain_header(diagnostic: &Diagnostic, path: &str) -> String {
+    let message = message(diagnostic);
+    if path.is_empty() {
+        return message;
+    }
+    return format!(
+        "{}({},{}): {}",
+        path, diagnostic.line, diagnostic.column, message
+    );
+}
+
+fn quickfix_line(diagnostic: &Diagnostic, path: &str) -> String {
+    let message = message(diagnostic);
+    if path.is_empty() {
+        return message;
+    }
+    return format!(
+        "{}:{}:{}: {}",
+        path, diagnostic.line, diagnostic.column, message
+    );
+}
+
+/// An `errorformat` for Vim to read quickfix lists with, like `:cfile` does, for lines
+/// with a code and for those without, like `path:1:1: error: message`. Emacs reads them
+/// out of the box, being in the format GNU tools report errors in.
+pub const ERRORFORMAT: &str = r"set errorformat=%f:%l:%c:\ %t%*[a-z]:\ %m,%f:%l:%c:\ %t%*[a-z]\ %m";
+
+fn pretty_header(diagnostic: &Diagnostic, path: &str) -> String {
+    let severity = format!(
+        "{}{}{}",
+        severity_color(diagnostic.severity),
+        diagnostic.severity.as_str(),
+        RESET
+    );
+    let code = match &diagnostic.code {
+        Some(code) => format!("{} {}: {}", GREY, code, RESET),
+        None => format!("{}: {}", GREY, RESET),
+    };
+    if path.is_empty() {
+        return format!("{}{}{}", severity, code, diagnostic.message);
+    }
+    return format!(
+        "{}{}{}:{}{}{}:{}{}{} - {}{}{}",
+        CYAN,
+        path,
+        RESET,
+        YELLOW,
+        diagnostic.line,
+        RESET,
+        YELLOW,
+        diagnostic.column,
+        RESET,
+        severity,
+        code,
+        diagnostic.message
+    );
+}
+
+fn message(diagnostic: &Diagnostic) -> String {
+    return match &diagnostic.code {
+        Some(code) => format!(
+            "{} {}: {}",
+            diagnostic.severity.as_str(),
+            code,
+            diagnostic.message
+        ),
+        None => format!("{}: {}", diagnostic.severity.as_str(), diagnostic.message),
+    };
+}
+
+fn severity_color(severity: Severity) -> &'static str {
+    return match severity {
+        Severity::Error => "\x1b[91m",
+        Severity::Warning => YELLOW,
+        Severity::Info => "\x1b[94m",
+    };
+}
+
+// Long messages go on over indented lines right below the first one, like
+//   Type '{ id: string; }' is not assignable to type 'Order'.
+//     Property 'total' is missing in type '{ id: string; }'.
+// Deno follows them with the code the diagnostic points at, underlined on the next line,
+// and an `at` line telling where it is, neither of which is part of the message
+pub(crate) fn message_chain(lines: &[String]) -> Vec<String> {
+    let lines: Vec<String> = lines.iter().skip(1).map(|line| strip_ansi(line)).collect();
+    let excerpt = |index: usize| lines.get(index + 1).is_some_and(|next| is_underline(next));
+    let location = |line: &str| {
+        let location = line.trim_start().strip_prefix("at ");
+        return location.and_then(parse_location).is_some();
+    };
+    return (lines.iter().enumerate())
+        .take_while(|(index, line)| {
+            line.starts_with(char::is_whitespace)
+                && !line.trim().is_empty()
+                && !excerpt(*index)
+                && !location(line)
+        })
+        .map(|(_, line)| line.clone())
+        .collect();
+}
+
+fn is_underline(line: &str) -> bool {
+    let line = line.trim();
+    return !line.is_empty() && line.chars().all(|c| c == '~');
+}
+
+// We don't know how long what the diagnostic points at is, so we underline the
+// identifier, number or string starting there, or a single character otherwise
+fn token_width(text: &str) -> usize {
+    let mut chars = text.chars();
+    let width = match chars.next() {
+        None => 0,
+        Some(quote @ ('"' | '\'' | '`')) => match chars.position(|c| c == quote) {
+            Some(end) => end + 2,
+            None => text.chars().count(),
+        },
+        Some(c) if is_word(c) => 1 + chars.take_while(|&c| is_word(c)).count(),
+        Some(_) => 1,
+    };
+    return width.max(1);
+}
+
+fn is_word(c: char) -> bool {
+    return c.is_alphanumeric() || c == '_' || c == '$';
+}
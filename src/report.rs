@@ -0,0 +1,430 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufWriter, Write},
+    sync::LazyLock,
+};
+
+use md5::{Digest, Md5};
+use regex::Regex;
+use serde_json::{Value, json};
+
+use crate::{Diagnostic, Error, Options, Outcome, Severity, html, markdown, paths::PathNormalizer};
+
+/// How long markdown reports get at most, in bytes. GitHub doesn't take
+/// comments longer than 65,536 characters.
+pub const MARKDOWN_LIMIT: usize = 60_000;
+
+/// A document written about the kept diagnostics once the input is over,
+/// instead of the lines they came in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Report {
+    /// Checkstyle XML, read by Jenkins, SonarQube and most CI servers
+    Checkstyle,
+    /// GitLab Code Quality JSON, shown in merge requests
+    Gitlab,
+    /// reviewdog's Diagnostic Format, as a single JSON document
+    Rdjson,
+    /// reviewdog's Diagnostic Format, one JSON diagnostic per line
+    Rdjsonl,
+    /// Markdown for pull request comments and job summaries
+    Markdown,
+    /// A single HTML page to browse, with the code around each diagnostic
+    Html,
+    /// SARIF 2.1.0, read by GitHub code scanning and most security dashboards
+    Sarif,
+    /// JUnit XML, with a failed test case per diagnostic, shown by CI servers as test results
+    Junit,
+}
+
+impl Report {
+    /// What the formats are called, as given to `--format` and `--report`.
+    pub const NAMES: &[&str] = &[
+        "checkstyle",
+        "gitlab",
+        "rdjson",
+        "rdjsonl",
+        "markdown",
+        "html",
+        "sarif",
+        "junit",
+    ];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        return match name {
+            "checkstyle" => Some(Report::Checkstyle),
+            "gitlab" => Some(Report::Gitlab),
+            "rdjson" => Some(Report::Rdjson),
+            "rdjsonl" => Some(Report::Rdjsonl),
+            "markdown" => Some(Report::Markdown),
+            "html" => Some(Report::Html),
+            "sarif" => Some(Report::Sarif),
+            "junit" => Some(Report::Junit),
+            _ => None,
+        };
+    }
+}
+
+/// What reports get to know besides the diagnostics themselves.
+pub struct Context<'a> {
+    /// Name of the tool the diagnostics came from, like `tsc`
+    pub source: &'a str,
+    /// How many diagnostics there were to begin with
+    pub outcome: &'a Outcome,
+    /// Absolute path of the directory the paths in the report are relative to
+    pub base: String,
+}
+
+impl<'a> Context<'a> {
+    pub fn new(options: &'a Options, outcome: &'a Outcome, normalizer: &PathNormalizer) -> Self {
+        let base = match &options.output_base {
+            Some(base) => normalizer.absolute(base),
+            None => normalizer.root().to_string(),
+        };
+        return Self {
+            source: options.input_format.as_deref().unwrap_or("tsef"),
+            outcome,
+            base,
+        };
+    }
+}
+
+/// Writes a report about `diagnostics`, whose paths are already the way they should
+/// read in it and whose messages include the lines their message went on over.
+pub fn write<W: Write>(
+    report: Report,
+    diagnostics: &[Diagnostic],
+    context: &Context,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    let source = context.source;
+    return match report {
+        Report::Markdown => markdown::write(diagnostics, context.outcome, MARKDOWN_LIMIT, writer),
+        Report::Html => html::write(diagnostics, context.outcome, &context.base, writer),
+        Report::Checkstyle => checkstyle(diagnostics, writer),
+        Report::Gitlab => gitlab(diagnostics, writer),
+        Report::Sarif => sarif(diagnostics, source, writer),
+        Report::Junit => junit(diagnostics, source, writer),
+        Report::Rdjson => {
+            let diagnostics: Vec<Value> = diagnostics.iter().map(|d| rdjson(d, source)).collect();
+            let document = json!({ "source": { "name": source }, "diagnostics": diagnostics });
+            let json = serde_json::to_string_pretty(&document).expect("JSON values serialize");
+            writeln!(writer, "{}", json)
+        }
+        Report::Rdjsonl => {
+            for diagnostic in diagnostics {
+                writeln!(writer, "{}", rdjson(diagnostic, source))?;
+            }
+            Ok(())
+        }
+    };
+}
+
+/// Makes sure every report file can be written, so that a wrong path gets
+/// told about before reading all of the input.
+pub fn check_files(reports: &[(Report, String)]) -> Result<(), Error> {
+    for (_, path) in reports {
+        File::create(path).map_err(|error| {
+            Error::InvalidReportPath(format!("Could not write {}: {}", path, error))
+        })?;
+    }
+    return Ok(());
+}
+
+/// Writes every report to the file it goes to, replacing whatever was there.
+pub fn write_files(
+    reports: &[(Report, String)],
+    diagnostics: &[Diagnostic],
+    context: &Context,
+) -> std::io::Result<()> {
+    for (report, path) in reports {
+        let with_path = |error: std::io::Error| {
+            std::io::Error::new(error.kind(), format!("Could not write {}: {}", path, error))
+        };
+        let mut file = BufWriter::new(File::create(path).map_err(with_path)?);
+        write(*report, diagnostics, context, &mut file).map_err(with_path)?;
+        file.flush().map_err(with_path)?;
+    }
+    return Ok(());
+}
+
+/// How the path of a diagnostic reads in reports: normalized, so that reports
+/// don't change with the way the tool printed it, or relative to `output_base`.
+pub(crate) fn path(normalizer: &PathNormalizer, path: &str, options: &Options) -> String {
+    return match &options.output_base {
+        _ if path.is_empty() => String::new(),
+        Some(base) => normalizer.relative_to(path, base),
+        None => normalizer.normalize(path),
+    };
+}
+
+// One <file> per path, in the order the paths first showed up in
+fn checkstyle<W: Write>(diagnostics: &[Diagnostic], writer: &mut W) -> std::io::Result<()> {
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(writer, r#"<checkstyle version="4.3">"#)?;
+    for (path, diagnostics) in by_file(diagnostics) {
+        writeln!(writer, r#"  <file name="{}">"#, escape_xml(path))?;
+        for diagnostic in diagnostics {
+            write!(
+                writer,
+                r#"    <error line="{}" column="{}" severity="{}" message="{}""#,
+                diagnostic.line,
+                diagnostic.column,
+                diagnostic.severity.as_str(),
+                escape_xml(&diagnostic.message)
+            )?;
+            if let Some(code) = &diagnostic.code {
+                write!(writer, r#" source="{}""#, escape_xml(code))?;
+            }
+            writeln!(writer, "/>")?;
+        }
+        writeln!(writer, "  </file>")?;
+    }
+    writeln!(writer, "</checkstyle>")?;
+    return Ok(());
+}
+
+// GitLab tells new issues from resolved ones by their fingerprints, so those must not
+// change when unrelated code moves an error to another line, and must be unique
+fn gitlab<W: Write>(diagnostics: &[Diagnostic], writer: &mut W) -> std::io::Result<()> {
+    let mut occurrences: HashMap<String, usize> = HashMap::new();
+    let issues: Vec<Value> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let mut key = format!(
+                "{}\0{}\0{}",
+                diagnostic.path,
+                diagnostic.code.as_deref().unwrap_or_default(),
+                normalize_message(&diagnostic.message)
+            );
+            let occurrence = occurrences.entry(key.clone()).or_default();
+            if *occurrence > 0 {
+                key.push_str(&format!("\0{}", occurrence));
+            }
+            *occurrence += 1;
+
+            let severity = match diagnostic.severity {
+                Severity::Error => "major",
+                Severity::Warning => "minor",
+                Severity::Info => "info",
+            };
+            return json!({
+                "description": diagnostic.message,
+                "check_name": diagnostic.code.as_deref().unwrap_or("tsef"),
+                "fingerprint": format!("{:x}", Md5::digest(key.as_bytes())),
+                "severity": severity,
+                "location": {
+                    "path": diagnostic.path,
+                    "lines": { "begin": diagnostic.line.max(1) },
+                },
+            });
+        })
+        .collect();
+    let json = serde_json::to_string_pretty(&issues).expect("JSON values serialize");
+    writeln!(writer, "{}", json)?;
+    return Ok(());
+}
+
+// One rule per code, in the order the codes first showed up in, so that results link
+// to the documentation of their code. Lines and columns start at 1 like in rdjson.
+fn sarif<W: Write>(
+    diagnostics: &[Diagnostic],
+    source: &str,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    let mut rules: Vec<Value> = Vec::new();
+    let mut indices: HashMap<&str, usize> = HashMap::new();
+    let results: Vec<Value> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let code = diagnostic.code.as_deref().unwrap_or(source);
+            let index = *indices.entry(code).or_insert_with(|| {
+                let mut rule = json!({ "id": code });
+                if let Some(url) = code_url(code, source) {
+                    rule["helpUri"] = json!(url);
+                }
+                rules.push(rule);
+                return rules.len() - 1;
+            });
+            let level = match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info => "note",
+            };
+            let mut result = json!({
+                "ruleId": code,
+                "ruleIndex": index,
+                "level": level,
+                "message": { "text": diagnostic.message },
+            });
+            if !diagnostic.path.is_empty() {
+                let mut location = json!({ "artifactLocation": { "uri": diagnostic.path } });
+                if diagnostic.line > 0 {
+                    location["region"] = json!({ "startLine": diagnostic.line });
+                    if diagnostic.column > 0 {
+                        location["region"]["startColumn"] = json!(diagnostic.column);
+                    }
+                }
+                result["locations"] = json!([{ "physicalLocation": location }]);
+            }
+            return result;
+        })
+        .collect();
+    let document = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": { "driver": { "name": source, "rules": rules } },
+            "results": results,
+        }],
+    });
+    let json = serde_json::to_string_pretty(&document).expect("JSON values serialize");
+    writeln!(writer, "{}", json)?;
+    return Ok(());
+}
+
+// One <testsuite> per path like the <file> of checkstyle, each diagnostic being a test
+// case that failed
+fn junit<W: Write>(
+    diagnostics: &[Diagnostic],
+    source: &str,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<testsuites name="{}" tests="{}" failures="{}">"#,
+        escape_xml(source),
+        diagnostics.len(),
+        diagnostics.len()
+    )?;
+    for (path, diagnostics) in by_file(diagnostics) {
+        writeln!(
+            writer,
+            r#"  <testsuite name="{}" tests="{}" failures="{}">"#,
+            escape_xml(path),
+            diagnostics.len(),
+            diagnostics.len()
+        )?;
+        for diagnostic in diagnostics {
+            let code = diagnostic.code.as_deref().unwrap_or(source);
+            let name = match diagnostic.line {
+                0 => code.to_string(),
+                line => format!("{} at {}:{}", code, line, diagnostic.column),
+            };
+            writeln!(
+                writer,
+                r#"    <testcase name="{}" classname="{}">"#,
+                escape_xml(&name),
+                escape_xml(path)
+            )?;
+            writeln!(
+                writer,
+                r#"      <failure type="{}" message="{}"/>"#,
+                diagnostic.severity.as_str(),
+                escape_xml(&diagnostic.message)
+            )?;
+            writeln!(writer, "    </testcase>")?;
+        }
+        writeln!(writer, "  </testsuite>")?;
+    }
+    writeln!(writer, "</testsuites>")?;
+    return Ok(());
+}
+
+// Lines and columns start at 1, so we leave out what we don't know
+fn rdjson(diagnostic: &Diagnostic, source: &str) -> Value {
+    let mut location = json!({ "path": diagnostic.path });
+    if diagnostic.line > 0 {
+        let mut start = json!({ "line": diagnostic.line });
+        if diagnostic.column > 0 {
+            start["column"] = json!(diagnostic.column);
+        }
+        location["range"] = json!({ "start": start });
+    }
+    let severity = match diagnostic.severity {
+        Severity::Error => "ERROR",
+        Severity::Warning => "WARNING",
+        Severity::Info => "INFO",
+    };
+    let mut value = json!({
+        "message": diagnostic.message,
+        "location": location,
+        "severity": severity,
+        "source": { "name": source },
+    });
+    if let Some(code) = &diagnostic.code {
+        value["code"] = json!({ "value": code });
+        if let Some(url) = code_url(code, source) {
+            value["code"]["url"] = json!(url);
+        }
+    }
+    return value;
+}
+
+/// Where a code is documented: TypeScript errors like `TS2322`, Angular's `NG` errors
+/// and, for diagnostics coming from `eslint`, its core rules and typescript-eslint's.
+pub fn code_url(code: &str, source: &str) -> Option<String> {
+    let digits = |rest: &str| !rest.is_empty() && rest.chars().all(|c| c.is_ascii_digit());
+    if let Some(number) = code.strip_prefix("TS").filter(|rest| digits(rest)) {
+        return Some(format!("https://typescript.tv/errors/#ts{}", number));
+    }
+    if code.strip_prefix("NG").is_some_and(digits) {
+        return Some(format!("https://angular.dev/errors/{}", code));
+    }
+    if source != "eslint" {
+        return None;
+    }
+    if let Some(rule) = code.strip_prefix("@typescript-eslint/") {
+        return Some(format!("https://typescript-eslint.io/rules/{}", rule));
+    }
+    // Rules of plugins are prefixed with the plugin's name
+    if code.chars().all(|c| c.is_ascii_lowercase() || c == '-') {
+        return Some(format!("https://eslint.org/docs/latest/rules/{}", code));
+    }
+    return None;
+}
+
+/// A message the way it goes into fingerprints, without the positions of related
+/// locations like `src/types.ts:12:3` and with its whitespace collapsed, so that
+/// moving code around or reflowing the message chain doesn't change them.
+pub fn normalize_message(message: &str) -> String {
+    static POSITIONS: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"(:\d+:\d+|\(\d+,\d+\))").expect("Valid regex"));
+    let message = POSITIONS.replace_all(message, "");
+    return message.split_whitespace().collect::<Vec<_>>().join(" ");
+}
+
+pub(crate) fn by_file(diagnostics: &[Diagnostic]) -> Vec<(&str, Vec<&Diagnostic>)> {
+    let mut files: Vec<(&str, Vec<&Diagnostic>)> = Vec::new();
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    for diagnostic in diagnostics {
+        let position = *positions.entry(&diagnostic.path).or_insert_with(|| {
+            files.push((&diagnostic.path, Vec::new()));
+            return files.len() - 1;
+        });
+        files[position].1.push(diagnostic);
+    }
+    return files;
+}
+
+/// Escapes text for an XML attribute. Line breaks and tabs are kept as character
+/// references, other control characters aren't allowed in XML and get dropped.
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\t' => escaped.push_str("&#9;"),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    return escaped;
+}
@@ -1,8 +1,10 @@
-use crate::{State, StateMachine, path_matches};
+use crate::{Diagnostic, State, StateMachine, path_matches};
 
 pub struct SimpleStateMachine {
     pub state: State,
     pub include: Vec<String>,
+    diagnostic: Option<Diagnostic>,
+    started: bool,
 }
 
 impl SimpleStateMachine {
@@ -10,12 +12,27 @@ impl SimpleStateMachine {
         return Self {
             state: State::ParseToPause,
             include,
+            diagnostic: None,
+            started: false,
         };
     }
+
+    fn track_diagnostic(&mut self, line: &str) {
+        // Every diagnostic starts on its own line, and long messages continue
+        // on the following lines with some indentation
+        self.started = false;
+        if let Some(diagnostic) = Diagnostic::from_plain(line) {
+            self.diagnostic = Some(diagnostic);
+            self.started = true;
+        } else if !line.starts_with(char::is_whitespace) || line.trim().is_empty() {
+            self.diagnostic = None;
+        }
+    }
 }
 
 impl StateMachine for SimpleStateMachine {
     fn run(&mut self, line: &String) -> (&State, bool) {
+        self.track_diagnostic(line);
         let path = line.split("(").next().expect("Received invalid output!");
         let should_block = !path_matches(&self.include, path);
         return (&self.state, !should_block);
@@ -24,4 +41,12 @@ impl StateMachine for SimpleStateMachine {
     fn is_finished(&self) -> bool {
         false
     }
+
+    fn diagnostic(&self) -> Option<&Diagnostic> {
+        self.diagnostic.as_ref()
+    }
+
+    fn starts_diagnostic(&self) -> bool {
+        self.started
+    }
 }
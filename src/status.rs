@@ -0,0 +1,22 @@
+use std::process::ExitCode;
+
+/// What a run of tsef comes down to, and the exit code it reports for it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// No errors are left after filtering
+    Clean = 0,
+    /// Some errors matched the filters
+    Errors = 1,
+    /// The arguments or the configuration are invalid
+    Usage = 2,
+    /// The input could not be read or parsed
+    Parse = 3,
+    /// The command tsef was asked to run crashed or could not be started
+    ChildCrashed = 4,
+}
+
+impl From<Status> for ExitCode {
+    fn from(status: Status) -> Self {
+        return ExitCode::from(status as u8);
+    }
+}
@@ -0,0 +1,244 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use regex::Regex;
+use toml::{Spanned, Table, Value};
+
+use crate::{Diagnostic, Error, check_patterns, path_matches};
+
+/// What the suppressions file is called, looked for under the root when none is given.
+pub const FILE_NAME: &str = "tsef-suppressions.toml";
+
+/// A reviewed exception listed in the suppressions file, like:
+///
+/// ```text
+/// [[suppression]]
+/// path = "src/legacy/**"
+/// code = "TS2345"
+/// message = "type 'string'"
+/// owner = "@payments-team"
+/// reason = "The legacy API takes order ids as strings"
+/// expires = 2026-12-31
+/// ```
+///
+/// It suppresses diagnostics with its code in the files matching its path, and whose
+/// message matches its regular expression if it has one, until the day it expires.
+#[derive(Clone, Debug)]
+pub struct Entry {
+    /// Line of the file the entry starts at
+    pub line: usize,
+    /// Glob pattern, relative to the root like the include patterns
+    pub path: String,
+    pub code: String,
+    pub message: Option<Regex>,
+    pub owner: String,
+    pub reason: String,
+    /// First day the entry no longer suppresses anything
+    pub expires: Date,
+    /// Whether it suppressed anything so far
+    pub used: bool,
+}
+
+impl Entry {
+    fn suppresses(&self, path: &str, diagnostic: &Diagnostic) -> bool {
+        return diagnostic.code.as_deref() == Some(self.code.as_str())
+            && path_matches(&vec![self.path.clone()], path)
+            && (self.message.as_ref()).is_none_or(|regex| regex.is_match(&diagnostic.message));
+    }
+}
+
+/// A day, as written in the suppressions file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: u32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// Reads a date like `2026-12-31`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.splitn(3, '-');
+        let mut part = |length: usize| {
+            let part = parts.next().filter(|part| part.len() == length)?;
+            return part.parse::<u32>().ok();
+        };
+        let date = Self {
+            year: part(4)?,
+            month: part(2)?,
+            day: part(2)?,
+        };
+        let valid = (1..=12).contains(&date.month) && (1..=31).contains(&date.day);
+        return valid.then_some(date);
+    }
+
+    /// The current day in UTC.
+    pub fn today() -> Self {
+        let seconds =
+            (SystemTime::now().duration_since(UNIX_EPOCH)).map_or(0, |duration| duration.as_secs());
+        return Self::from_days((seconds / 86_400) as i64);
+    }
+
+    // Days since 1970-01-01 to a date of the Gregorian calendar, counting in eras of
+    // 400 years starting on March 1st so that leap days come last
+    fn from_days(days: i64) -> Self {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        return Self {
+            year: year as u32,
+            month: month as u32,
+            day: day as u32,
+        };
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day);
+    }
+}
+
+/// The entries of a suppressions file, split between the live ones and those past
+/// their expiry date, which don't suppress anything anymore.
+#[derive(Debug)]
+pub struct SuppressionFile {
+    /// Path of the file, the way it gets printed
+    pub path: String,
+    pub entries: Vec<Entry>,
+    today: Date,
+}
+
+impl SuppressionFile {
+    /// Reads the file at `path`, shown as `display` in messages.
+    pub fn load(path: &str, display: &str) -> Result<Self, Error> {
+        let text = std::fs::read_to_string(path).map_err(|error| {
+            Error::InvalidSuppressions(format!("Could not read {}: {}", display, error))
+        })?;
+        return Self::parse(display, &text, Date::today());
+    }
+
+    /// Reads the entries of a suppressions file, telling which ones expired by `today`.
+    pub fn parse(path: &str, text: &str, today: Date) -> Result<Self, Error> {
+        let invalid =
+            |message: String| Error::InvalidSuppressions(format!("{}: {}", path, message));
+        let mut file: BTreeMap<String, Vec<Spanned<Table>>> = toml::from_str(text)
+            .map_err(|error| invalid(error.to_string().trim_end().to_string()))?;
+
+        let mut entries = Vec::new();
+        for table in file.remove("suppression").unwrap_or_default() {
+            let line = text[..table.span().start].matches('\n').count() + 1;
+            let entry = entry(table.get_ref(), line).map_err(|message| {
+                invalid(format!("The suppression on line {} {}", line, message))
+            })?;
+            entries.push(entry);
+        }
+        if let Some(key) = file.keys().next() {
+            return Err(invalid(format!(
+                "Unknown key \"{}\", suppressions are listed as [[suppression]]",
+                key
+            )));
+        }
+        return Ok(Self {
+            path: path.to_string(),
+            entries,
+            today,
+        });
+    }
+
+    /// Whether a live entry suppresses the diagnostic, whose path is normalized,
+    /// which marks that entry as used.
+    pub fn suppress(&mut self, path: &str, diagnostic: &Diagnostic) -> bool {
+        let today = self.today;
+        let Some(entry) = (self.entries.iter_mut())
+            .find(|entry| entry.expires > today && entry.suppresses(path, diagnostic))
+        else {
+            return false;
+        };
+        entry.used = true;
+        return true;
+    }
+
+    /// The entries past their expiry date.
+    pub fn expired(&self) -> Vec<&Entry> {
+        return (self.entries.iter())
+            .filter(|entry| entry.expires <= self.today)
+            .collect();
+    }
+
+    /// The live entries that didn't suppress anything.
+    pub fn unused(&self) -> Vec<&Entry> {
+        return (self.entries.iter())
+            .filter(|entry| entry.expires > self.today && !entry.used)
+            .collect();
+    }
+}
+
+// Everything but the message is required, so that each exception says who to ask about it
+// and until when it holds
+fn entry(table: &Table, line: usize) -> Result<Entry, String> {
+    const KEYS: &[&str] = &["path", "code", "message", "owner", "reason", "expires"];
+    if let Some(key) = table.keys().find(|key| !KEYS.contains(&key.as_str())) {
+        return Err(format!("has an unknown key \"{}\"", key));
+    }
+    let text = |key: &str| -> Result<Option<String>, String> {
+        return match table.get(key) {
+            None => Ok(None),
+            Some(Value::String(text)) if !text.trim().is_empty() => Ok(Some(text.clone())),
+            Some(_) => Err(format!("needs some text as its {}", key)),
+        };
+    };
+    let required = |key: &str| -> Result<String, String> {
+        return text(key)?.ok_or_else(|| format!("is missing its {}", key));
+    };
+
+    let path = required("path")?;
+    check_patterns(std::slice::from_ref(&path))
+        .map_err(|error| format!("has an invalid path: {}", error))?;
+    let code = required("code")?;
+    let message = match text("message")? {
+        Some(message) => Some(
+            Regex::new(&message)
+                .map_err(|error| format!("has an invalid message regex: {}", error))?,
+        ),
+        None => None,
+    };
+    let owner = required("owner")?;
+    let reason = required("reason")?;
+    let expires = match table.get("expires") {
+        None => return Err("is missing its expiry date".to_string()),
+        Some(Value::Datetime(datetime)) => datetime.date.map(|date| Date {
+            year: date.year.into(),
+            month: date.month.into(),
+            day: date.day.into(),
+        }),
+        Some(Value::String(text)) => Date::parse(text),
+        Some(_) => None,
+    };
+    let expires = expires.ok_or("needs a date like 2026-12-31 as its expiry date")?;
+
+    return Ok(Entry {
+        line,
+        path,
+        code,
+        message,
+        owner,
+        reason,
+        expires,
+        used: false,
+    });
+}
@@ -0,0 +1,169 @@
+use std::{collections::HashMap, path::Path, sync::LazyLock};
+
+use regex::Regex;
+
+use crate::{Diagnostic, path_matches};
+
+/// A `tsef-ignore` comment found in a source file, like:
+///
+/// ```text
+/// // tsef-ignore TS2345 reason: the legacy API takes strings
+/// processOrder("123");
+/// ```
+///
+/// It suppresses diagnostics with one of its codes on the line below it, or on its own
+/// line when it follows some code. `tsef-ignore-file` suppresses them in the whole file.
+/// Without codes, any diagnostic is suppressed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Suppression {
+    /// Line of the comment, starting at 1
+    pub line: usize,
+    /// Line whose diagnostics are suppressed, or none for the whole file
+    pub target: Option<usize>,
+    pub codes: Vec<String>,
+    /// The comment itself, without the characters opening and closing it
+    pub text: String,
+    /// Whether it suppressed anything so far
+    pub used: bool,
+}
+
+impl Suppression {
+    fn suppresses(&self, diagnostic: &Diagnostic) -> bool {
+        let line_matches = self.target.is_none_or(|line| line == diagnostic.line);
+        let code_matches = self.codes.is_empty()
+            || (diagnostic.code.as_ref()).is_some_and(|code| self.codes.contains(code));
+        return line_matches && code_matches;
+    }
+}
+
+static COMMENT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(//|/\*|<!--)\s*(tsef-ignore(-file)?\b.*?)\s*(\*/\}?|-->)?\s*$")
+        .expect("Valid regex")
+});
+
+/// Finds the `tsef-ignore` and `tsef-ignore-file` comments of a source file.
+pub fn parse(source: &str) -> Vec<Suppression> {
+    let mut suppressions = Vec::new();
+    for (index, line) in source.lines().enumerate() {
+        let Some(captures) = COMMENT.captures(line) else {
+            continue;
+        };
+        let text = captures[2].to_string();
+        let whole_file = captures.get(3).is_some();
+        let follows_code = !line[..captures.get(1).expect("Comment start").start()]
+            .trim()
+            .trim_end_matches('{')
+            .is_empty();
+
+        // Codes come first, anything after `reason:` or `--` is for the reader
+        let rest = text.split_once(' ').map_or("", |(_, rest)| rest);
+        let codes = rest
+            .split([' ', ','])
+            .take_while(|word| !word.starts_with("reason") && *word != "--")
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_string())
+            .collect();
+
+        let target = match (whole_file, follows_code) {
+            (true, _) => None,
+            (false, true) => Some(index + 1),
+            (false, false) => Some(index + 2),
+        };
+        suppressions.push(Suppression {
+            line: index + 1,
+            target,
+            codes,
+            text,
+            used: false,
+        });
+    }
+    return suppressions;
+}
+
+// Sources worth looking into for comments when checking for unused ones
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs", "vue", "svelte", "html",
+];
+
+/// The `tsef-ignore` comments of every source file we looked into, read the first
+/// time a diagnostic points into the file.
+#[derive(Default)]
+pub struct Suppressions {
+    files: HashMap<String, Vec<Suppression>>,
+}
+
+impl Suppressions {
+    /// Whether a comment in the file at `path` suppresses the diagnostic, which
+    /// marks that comment as used.
+    pub fn suppress(&mut self, path: &str, diagnostic: &Diagnostic) -> bool {
+        let suppressions = self
+            .files
+            .entry(path.to_string())
+            .or_insert_with(|| read(Path::new(path)));
+        let Some(suppression) = suppressions.iter_mut().find(|s| s.suppresses(diagnostic)) else {
+            return false;
+        };
+        suppression.used = true;
+        return true;
+    }
+
+    /// The comments that didn't suppress anything, looking into every source file under
+    /// `root` whose path relative to it matches `include`, along with the ones read so far.
+    /// Dependencies and hidden directories are left out. Sorted by path, then by line.
+    pub fn unused(&mut self, root: &str, include: &Vec<String>) -> Vec<(String, Suppression)> {
+        let mut paths = Vec::new();
+        find_sources(Path::new(root), &mut paths);
+        for path in paths {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            if path_matches(include, &relative) {
+                let path = path.to_string_lossy().to_string();
+                let suppressions = read(Path::new(&path));
+                self.files.entry(path).or_insert(suppressions);
+            }
+        }
+
+        let mut unused: Vec<(String, Suppression)> = self
+            .files
+            .iter()
+            .flat_map(|(path, suppressions)| {
+                suppressions
+                    .iter()
+                    .filter(|suppression| !suppression.used)
+                    .map(|suppression| (path.clone(), suppression.clone()))
+            })
+            .collect();
+        unused.sort_by(|(a, a_suppression), (b, b_suppression)| {
+            (a, a_suppression.line).cmp(&(b, b_suppression.line))
+        });
+        return unused;
+    }
+}
+
+fn read(path: &Path) -> Vec<Suppression> {
+    return match std::fs::read_to_string(path) {
+        Ok(source) if source.contains("tsef-ignore") => parse(&source),
+        _ => Vec::new(),
+    };
+}
+
+fn find_sources(directory: &Path, paths: &mut Vec<std::path::PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(directory) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() && !name.starts_with('.') && name != "node_modules" {
+            find_sources(&path, paths);
+        }
+        let is_source = (path.extension().and_then(|e| e.to_str()))
+            .is_some_and(|extension| SOURCE_EXTENSIONS.contains(&extension));
+        if file_type.is_file() && is_source {
+            paths.push(path);
+        }
+    }
+}
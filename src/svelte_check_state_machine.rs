@@ -0,0 +1,224 @@
+use crate::{
+    Diagnostic, Severity, State, StateMachine,
+    diagnostic::{parse_location, strip_ansi},
+    paths::is_absolute,
+};
+
+/// Parses the output of `svelte-check`, which comes in two flavours.
+///
+/// The human format prints the location on a line of its own, followed by the message
+/// and a code excerpt, and ends with a summary:
+///
+/// /home/me/project/src/routes/+page.svelte:12:5
+/// Error: Type 'string' is not assignable to type 'number'. (ts)
+///
+/// svelte-check found 1 error and 0 warnings in 1 file
+///
+/// The machine format (`--output machine`) prints one diagnostic per line, with paths
+/// relative to the workspace announced on the first line:
+///
+/// 1590680326283 START "/home/me/project"
+/// 1590680326283 ERROR "src/routes/+page.svelte" 12:5 "Type 'string' is not assignable ..."
+/// 1590680326807 COMPLETED 20 FILES 1 ERRORS 0 WARNINGS 1 FILES_WITH_PROBLEMS
+pub struct SvelteCheckStateMachine {
+    pub state: State,
+    pub include: Vec<String>,
+    workspace: Option<String>,
+    diagnostic: Option<Diagnostic>,
+    started: bool,
+    // Set on the location line of the human format, whose message comes on the next line
+    awaiting_message: bool,
+    blank_line_seen: bool,
+}
+
+impl SvelteCheckStateMachine {
+    pub fn new(include: Vec<String>) -> Self {
+        return Self {
+            state: State::ParseToPause,
+            include,
+            workspace: None,
+            diagnostic: None,
+            started: false,
+            awaiting_message: false,
+            blank_line_seen: false,
+        };
+    }
+
+    /// Whether a line is how svelte-check starts its output, in either format.
+    pub fn detect(line: &str) -> bool {
+        let line = strip_ansi(line);
+        return line.starts_with("====")
+            || line.starts_with("Loading svelte-check")
+            || machine_line(&line).is_some_and(|(kind, _)| kind == "START");
+    }
+
+    fn start(&mut self, diagnostic: Diagnostic) {
+        self.diagnostic = Some(diagnostic);
+        self.started = true;
+        self.blank_line_seen = false;
+    }
+
+    fn parse_machine(&mut self, kind: &str, rest: &str) {
+        if kind == "START" {
+            self.workspace = parse_quoted(rest).map(|(workspace, _)| workspace);
+            return;
+        }
+        if kind == "COMPLETED" {
+            self.state = State::End;
+            return;
+        }
+
+        let severity = match Severity::parse(kind) {
+            Some(severity) => severity,
+            None => return,
+        };
+        let Some((path, rest)) = parse_quoted(rest) else {
+            return;
+        };
+        let (position, rest) = rest.trim_start().split_once(' ').unwrap_or((rest, ""));
+        let Some((line, column)) = position.split_once(':') else {
+            return;
+        };
+        let (Ok(line), Ok(column)) = (line.parse(), column.parse()) else {
+            return;
+        };
+
+        let path = match &self.workspace {
+            Some(workspace) if !is_absolute(&path) => format!("{}/{}", workspace, path),
+            _ => path,
+        };
+        let mut diagnostic = Diagnostic::new(&path, line, column);
+        diagnostic.severity = severity;
+        diagnostic.message = parse_quoted(rest).map(|(m, _)| m).unwrap_or_default();
+        self.start(diagnostic);
+    }
+
+    fn parse_human(&mut self, line: &str) {
+        if line.starts_with("svelte-check found") {
+            self.state = State::End;
+            return;
+        }
+
+        // A location on a line of its own starts a new diagnostic
+        if let Some((path, row, column)) = parse_location(line.trim()) {
+            if !path.contains(": ") {
+                self.start(Diagnostic::new(path, row, column));
+                self.awaiting_message = true;
+                return;
+            }
+        }
+
+        let Some(diagnostic) = self.diagnostic.as_mut() else {
+            return;
+        };
+        if line.trim().is_empty() {
+            self.blank_line_seen = true;
+            return;
+        }
+        if self.blank_line_seen || line.starts_with("====") {
+            self.diagnostic = None;
+            return;
+        }
+
+        // The line right after the location tells what went wrong, like
+        // `Error: Cannot find name 'foo'. (ts)`, the rest is a code excerpt
+        if diagnostic.message.is_empty() {
+            if let Some((severity, message)) = line.split_once(": ") {
+                if let Some(severity) = Severity::parse(severity) {
+                    diagnostic.severity = severity;
+                    diagnostic.message = strip_source(message).to_string();
+                }
+            }
+        }
+    }
+}
+
+impl StateMachine for SvelteCheckStateMachine {
+    fn run(&mut self, line: &String) -> (&State, bool) {
+        self.started = false;
+        self.awaiting_message = false;
+        if self.state == State::End {
+            self.diagnostic = None;
+            return (&self.state, true);
+        }
+
+        let line = strip_ansi(line);
+        match machine_line(&line) {
+            Some((kind, rest)) => {
+                self.diagnostic = None;
+                self.parse_machine(kind, rest);
+            }
+            None => self.parse_human(&line),
+        }
+        if self.state == State::End {
+            self.diagnostic = None;
+            return (&self.state, true);
+        }
+
+        // Lines that aren't part of any diagnostic, like the banner, only
+        // make it through when we aren't filtering anything
+        return (&self.state, self.include.is_empty());
+    }
+
+    fn is_finished(&self) -> bool {
+        self.state == State::End
+    }
+
+    fn diagnostic(&self) -> Option<&Diagnostic> {
+        self.diagnostic.as_ref()
+    }
+
+    fn starts_diagnostic(&self) -> bool {
+        self.started
+    }
+
+    // The location line is held back along with the diagnostic until the next line
+    // tells its severity and message, so that those get matched and reported too
+    fn awaits_location(&self) -> bool {
+        self.awaiting_message
+    }
+}
+
+// Messages end with the part of svelte-check that found them, like `(ts)` or `(svelte)`
+fn strip_source(message: &str) -> &str {
+    for source in [" (ts)", " (js)", " (svelte)", " (css)"] {
+        if let Some(message) = message.strip_suffix(source) {
+            return message;
+        }
+    }
+    return message;
+}
+
+// Machine lines start with a timestamp and a kind, like `1590680326283 ERROR ...`
+fn machine_line(line: &str) -> Option<(&str, &str)> {
+    let (timestamp, rest) = line.split_once(' ')?;
+    if timestamp.is_empty() || !timestamp.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let (kind, rest) = rest.split_once(' ').unwrap_or((rest, ""));
+    if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_uppercase() || c == '_') {
+        return None;
+    }
+    return Some((kind, rest));
+}
+
+// Reads a JSON style quoted string off the start of the text, returning it
+// unescaped along with whatever follows it
+fn parse_quoted(text: &str) -> Option<(String, &str)> {
+    let text = text.trim_start().strip_prefix('"')?;
+    let mut value = String::new();
+    let mut chars = text.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &text[index + 1..])),
+            '\\' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                'r' => value.push('\r'),
+                other => value.push(other),
+            },
+            _ => value.push(c),
+        }
+    }
+    return None;
+}
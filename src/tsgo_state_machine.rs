@@ -0,0 +1,102 @@
+use ansi_parser::{AnsiParser, Output};
+
+use crate::{Diagnostic, Severity, State, StateMachine, diagnostic::strip_ansi, path_matches};
+
+/// Parses the pretty output of the native TypeScript compiler (`tsgo`).
+///
+/// Its diagnostics look like the ones of `tsc --pretty`, but it colors them differently
+/// and doesn't leave the same blank lines before its summary, which throws off the way
+/// `AnsiStateMachine` detects the end of the diagnostics. So instead of relying on a
+/// particular escape sequence and on counting blank lines, we read the text itself:
+///
+/// • A line starting with an escape sequence and reading like
+///   `src/index.ts:3:7 - error TS2322: ...` starts a new diagnostic
+/// • A line reading like `Found 3 errors in 2 files.` starts the summary
+/// • Everything in between belongs to the diagnostic above it
+///
+/// Plain `tsgo` output is the same as plain `tsc` output, and goes through `SimpleStateMachine`.
+pub struct TsgoStateMachine {
+    pub state: State,
+    pub include: Vec<String>,
+    diagnostic: Option<Diagnostic>,
+    started: bool,
+}
+
+impl TsgoStateMachine {
+    pub fn new(include: Vec<String>) -> Self {
+        return Self {
+            state: State::ParseToPause,
+            include,
+            diagnostic: None,
+            started: false,
+        };
+    }
+
+    /// Whether a line is how pretty `tsgo` output starts: a diagnostic whose path is
+    /// colored cyan, where `tsc --pretty` uses bright cyan.
+    pub fn detect(line: &str) -> bool {
+        return line.starts_with(PATH_COLOR) && Self::parse_header(line).is_some();
+    }
+
+    fn parse_header(line: &str) -> Option<Diagnostic> {
+        let starts_with_escape = matches!(line.ansi_parse().next(), Some(Output::Escape(_)));
+        if !starts_with_escape {
+            return None;
+        }
+        let text = strip_ansi(line);
+        let (_, message) = text.split_once(" - ")?;
+        Severity::parse(message.split(' ').next()?)?;
+        return Diagnostic::from_pretty(&text);
+    }
+}
+
+// tsc colors paths with `\x1b[96m` instead
+const PATH_COLOR: &str = "\u{1b}[36m";
+
+/// Whether a line is the start of the summary tsc and tsgo print after the diagnostics,
+/// like `Found 1 error in src/index.ts:3` or `Found 3 errors in 2 files.`
+pub fn is_summary(line: &str) -> bool {
+    let line = strip_ansi(line);
+    let Some(rest) = line.trim_start().strip_prefix("Found ") else {
+        return false;
+    };
+    let count = rest.split(' ').next().unwrap_or("");
+    return !count.is_empty()
+        && count.chars().all(|c| c.is_ascii_digit())
+        && rest[count.len()..].starts_with(" error");
+}
+
+impl StateMachine for TsgoStateMachine {
+    fn run(&mut self, line: &String) -> (&State, bool) {
+        self.started = false;
+        if self.state != State::End && is_summary(line) {
+            self.state = State::End;
+        }
+        if self.state == State::End {
+            self.diagnostic = None;
+            return (&self.state, true);
+        }
+
+        if let Some(diagnostic) = Self::parse_header(line) {
+            let should_print = path_matches(&self.include, &diagnostic.path);
+            self.diagnostic = Some(diagnostic);
+            self.started = true;
+            return (&self.state, should_print);
+        }
+
+        // Whatever comes before the first diagnostic, like the watch mode banner, is printed
+        return (&self.state, self.diagnostic.is_none());
+    }
+
+    fn is_finished(&self) -> bool {
+        self.state == State::End
+    }
+
+    fn diagnostic(&self) -> Option<&Diagnostic> {
+        self.diagnostic.as_ref()
+    }
+
+    fn starts_diagnostic(&self) -> bool {
+        self.started
+    }
+}
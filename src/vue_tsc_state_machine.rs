@@ -0,0 +1,73 @@
+use crate::{Diagnostic, State, StateMachine};
+
+/// `vue-tsc` prints its diagnostics just like `tsc` does, pretty or not, so it is
+/// parsed by the same state machines. What differs are the paths: besides `.vue`
+/// files themselves, errors can point into the virtual TypeScript files generated
+/// for a component, like `src/App.vue.ts` or `src/App.vue.__VLS_template.ts`. We map
+/// those back to the component, so that include patterns like `src/**/*.vue` apply.
+///
+/// The output alone doesn't tell vue-tsc and tsc apart, so every tsc-like input goes
+/// through this state machine. Only the suffixes of virtual files get mapped, other
+/// paths are left as they are, declaration files like `src/App.vue.d.ts` included.
+pub struct VueTscStateMachine {
+    inner: Box<dyn StateMachine>,
+    state: State,
+    diagnostic: Option<Diagnostic>,
+}
+
+impl VueTscStateMachine {
+    pub fn new(inner: Box<dyn StateMachine>) -> Self {
+        return Self {
+            inner,
+            state: State::ParseToPause,
+            diagnostic: None,
+        };
+    }
+}
+
+/// The path of the single file component a (possibly virtual) path belongs to: the
+/// component itself for `App.vue.ts`, `App.vue.__VLS_template.ts` and `App.vue?vue&...`.
+pub fn component_path(path: &str) -> &str {
+    if let Some(index) = path.find(".vue?") {
+        return &path[..index + ".vue".len()];
+    }
+    if let Some(component) = path.strip_suffix(".ts") {
+        if component.ends_with(".vue") {
+            return component;
+        }
+        if let Some(index) = component.rfind(".vue.__VLS_") {
+            return &path[..index + ".vue".len()];
+        }
+    }
+    return path;
+}
+
+impl StateMachine for VueTscStateMachine {
+    fn run(&mut self, line: &String) -> (&State, bool) {
+        let (state, should_print) = self.inner.run(line);
+        self.state = *state;
+
+        // Line and column are left alone, vue-tsc already maps them to the component
+        self.diagnostic = self.inner.diagnostic().map(|diagnostic| Diagnostic {
+            path: component_path(&diagnostic.path).to_string(),
+            ..diagnostic.clone()
+        });
+        return (&self.state, should_print);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn diagnostic(&self) -> Option<&Diagnostic> {
+        self.diagnostic.as_ref()
+    }
+
+    fn starts_diagnostic(&self) -> bool {
+        self.inner.starts_diagnostic()
+    }
+
+    fn parse_error(&self) -> Option<&str> {
+        self.inner.parse_error()
+    }
+}
@@ -0,0 +1,55 @@
+// Helpers shared by the test files, each of them including this module
+#![allow(dead_code)]
+
+use tsef::{Options, Outcome, Severity, StateMachine, Status, filter};
+
+/// Filters `input`, returning what got written and the verdict.
+pub fn run(input: &str, options: &Options) -> (String, Status) {
+    let (output, outcome) = run_with_outcome(input, options);
+    (output, outcome.verdict)
+}
+
+/// Filters `input`, returning what got written and the whole outcome.
+pub fn run_with_outcome(input: &str, options: &Options) -> (String, Outcome) {
+    let mut output = Vec::new();
+    let outcome = filter(input.as_bytes(), &mut output, options).unwrap();
+    (String::from_utf8(output).unwrap(), outcome)
+}
+
+/// Filters `input`, returning only what got written.
+pub fn filtered(input: &str, options: &Options) -> String {
+    run(input, options).0
+}
+
+/// Options keeping what matches one of the patterns.
+pub fn include(patterns: &[&str]) -> Options {
+    Options {
+        include: patterns.iter().map(|p| p.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+/// Same as `include`, with the patterns relative to `root`.
+pub fn include_under(root: &str, patterns: &[&str]) -> Options {
+    Options {
+        root: Some(root.to_string()),
+        ..include(patterns)
+    }
+}
+
+/// Path, line, severity and code of every diagnostic the state machine finds in
+/// `input`, once it knows where the diagnostic is.
+pub fn diagnostics(sm: &mut dyn StateMachine, input: &str) -> Vec<(String, usize, Severity, Option<String>)> {
+    let mut diagnostics = Vec::new();
+    let mut awaiting = false;
+    for line in input.lines() {
+        sm.run(&line.to_string());
+        if let Some(d) = sm.diagnostic() {
+            if (sm.starts_diagnostic() || awaiting) && !sm.awaits_location() {
+                diagnostics.push((d.path.clone(), d.line, d.severity, d.code.clone()));
+            }
+        }
+        awaiting = sm.awaits_location();
+    }
+    diagnostics
+}
@@ -1,5 +1,34 @@
+// Each test file includes the shared helpers in tests/common on its own, so that it
+// also builds as a test of its own
+#![allow(clippy::duplicate_mod)]
+
 mod test_path_matching;
 mod test_simple_state_machine;
 mod test_ansi_state_machine;
 mod test_cli_integration;
 mod test_edge_cases;
+mod test_limits;
+mod test_paths;
+mod test_pipeline;
+mod test_filter;
+mod test_vue_tsc;
+mod test_diagnostic;
+mod test_svelte_check;
+mod test_tsgo;
+mod test_deno;
+mod test_eslint;
+mod test_angular;
+mod test_registry;
+mod test_pattern;
+mod test_render;
+mod test_checkstyle;
+mod test_gitlab;
+mod test_rdjson;
+mod test_markdown;
+mod test_html;
+mod test_sarif;
+mod test_junit;
+mod test_report_files;
+mod test_dedupe;
+mod test_suppressions;
+mod test_suppression_file;
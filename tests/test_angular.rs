@@ -0,0 +1,84 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::{diagnostics, include, run};
+use tsef::{Options, Severity, Status, angular_state_machine::AngularStateMachine};
+
+#[cfg(test)]
+mod angular_tests {
+    use super::*;
+
+    const CLI_OUTPUT: &str = include_str!("data/angular_cli_output.txt");
+    const NGC_OUTPUT: &str = include_str!("data/ngc_output.txt");
+
+    #[test]
+    fn test_detect() {
+        assert!(AngularStateMachine::detect("✘ [ERROR] NG8002: Can't bind to 'total'. [plugin angular-compiler]"));
+        assert!(AngularStateMachine::detect("X [ERROR] TS2322: Type 'string' is not assignable."));
+        assert!(AngularStateMachine::detect("src/app/app.component.html(3,5): error NG8001: 'app-nav' is not a known element."));
+        assert!(!AngularStateMachine::detect("src/app/app.component.ts(3,5): error TS2322: Type."));
+    }
+
+    #[test]
+    fn test_cli_diagnostics() {
+        assert_eq!(
+            diagnostics(&mut AngularStateMachine::new(vec![]), CLI_OUTPUT),
+            vec![
+                ("src/app/billing/invoice.component.html".to_string(), 12, Severity::Error, Some("NG8002".to_string())),
+                ("src/app/billing/invoice.service.ts".to_string(), 8, Severity::Error, Some("TS2322".to_string())),
+                ("src/app/shell/header.component.html".to_string(), 3, Severity::Warning, Some("NG8107".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ngc_diagnostics() {
+        assert_eq!(
+            diagnostics(&mut AngularStateMachine::new(vec![]), NGC_OUTPUT),
+            vec![
+                ("src/app/billing/invoice.component.html".to_string(), 12, Severity::Error, Some("NG8002".to_string())),
+                ("src/app/shell/header.component.ts".to_string(), 9, Severity::Error, Some("TS2322".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cli_templates_and_components_match_the_same_glob() {
+        let (output, status) = run(CLI_OUTPUT, &include(&["src/app/billing/**"]));
+        assert_eq!(status, Status::Errors);
+        assert!(output.contains("NG8002"));
+        assert!(output.contains("Error occurs in the template of component InvoiceComponent."));
+        assert!(output.contains("src/app/billing/invoice.service.ts:8:4:"));
+        assert!(!output.contains("NG8107"));
+        assert!(!output.contains("Application bundle generation failed."));
+    }
+
+    #[test]
+    fn test_cli_related_location_does_not_move_the_diagnostic() {
+        let (output, status) = run(CLI_OUTPUT, &include(&["src/app/billing/invoice.component.ts"]));
+        assert_eq!(status, Status::Clean);
+        assert_eq!(output, "");
+    }
+
+    #[test]
+    fn test_ngc_filter() {
+        let (output, status) = run(NGC_OUTPUT, &include(&["src/app/billing/**"]));
+        assert_eq!(status, Status::Errors);
+        assert!(output.contains("NG8002"));
+        assert!(output.contains("Error occurs in the template of component InvoiceComponent."));
+        assert!(!output.contains("header.component.ts"));
+    }
+
+    #[test]
+    fn test_code_filter() {
+        let options = Options {
+            codes: vec!["NG8002".to_string(), "NG8107".to_string()],
+            ..Default::default()
+        };
+        let (output, _) = run(CLI_OUTPUT, &options);
+        assert!(output.contains("NG8002"));
+        assert!(output.contains("NG8107"));
+        assert!(!output.contains("TS2322"));
+        assert!(output.contains("Application bundle generation failed."));
+    }
+}
@@ -236,4 +236,19 @@ mod ansi_state_machine_tests {
         assert_eq!(*state, State::ParseToPause); // Matching path, no flip
         assert!(should_print);
     }
+
+    #[test]
+    fn test_missing_path_is_a_parse_error() {
+        let identifier = create_test_ansi_sequence();
+        let include = vec!["src/**/*".to_string()];
+        let mut sm = AnsiStateMachine::new(identifier, include);
+
+        // Our identifier followed by another sequence instead of a path
+        sm.run(&"\u{001b}[96m\u{001b}[0m:15:7 - error TS2322".to_string());
+        assert!(sm.parse_error().is_some());
+
+        // The error only sticks to the line that caused it
+        sm.run(&create_ansi_line_with_path("src/components/Header.tsx"));
+        assert!(sm.parse_error().is_none());
+    }
 }
\ No newline at end of file
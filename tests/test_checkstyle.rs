@@ -0,0 +1,117 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::{include, run};
+use tsef::{Options, Report, Status};
+
+#[cfg(test)]
+mod checkstyle_tests {
+    use super::*;
+
+    #[test]
+    fn test_groups_errors_by_file() {
+        let input = "src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     lib/b.ts(2,3): warning TS6133: 'x' is declared but its value is never read.\n\
+                     src/a.ts(5,1): error TS1005: ';' expected.\n\
+                     Found 3 errors in 2 files.\n";
+
+        let (output, verdict) = run(input, &Options { report: Some(Report::Checkstyle), ..include(&[]) });
+
+        assert_eq!(verdict, Status::Errors);
+        assert_eq!(
+            output,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <checkstyle version=\"4.3\">\n  \
+               <file name=\"src/a.ts\">\n    \
+                 <error line=\"1\" column=\"7\" severity=\"error\" message=\"Type &apos;string&apos; is not assignable to type &apos;number&apos;.\" source=\"TS2322\"/>\n    \
+                 <error line=\"5\" column=\"1\" severity=\"error\" message=\"&apos;;&apos; expected.\" source=\"TS1005\"/>\n  \
+               </file>\n  \
+               <file name=\"lib/b.ts\">\n    \
+                 <error line=\"2\" column=\"3\" severity=\"warning\" message=\"&apos;x&apos; is declared but its value is never read.\" source=\"TS6133\"/>\n  \
+               </file>\n\
+             </checkstyle>\n"
+        );
+    }
+
+    #[test]
+    fn test_only_kept_errors_are_reported() {
+        let input = "src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     node_modules/lib/index.d.ts(2,3): error TS2300: Duplicate identifier 'x'.\n";
+
+        let (output, _) = run(input, &Options { report: Some(Report::Checkstyle), ..include(&["src/**/*"]) });
+
+        assert!(output.contains("<file name=\"src/a.ts\">"));
+        assert!(!output.contains("node_modules"));
+    }
+
+    #[test]
+    fn test_message_chain_and_markup_are_escaped() {
+        let input = "src/a.ts(1,7): error TS2322: Type '{ a: \"<b>\" & C; }' is not assignable.\n  \
+                     Property 'total' is missing.\n";
+
+        let (output, _) = run(input, &Options { report: Some(Report::Checkstyle), ..include(&[]) });
+
+        assert!(output.contains(
+            "message=\"Type &apos;{ a: &quot;&lt;b&gt;&quot; &amp; C; }&apos; is not assignable.&#10;Property &apos;total&apos; is missing.\""
+        ));
+    }
+
+    #[test]
+    fn test_message_chain_without_message() {
+        let input = "src/a.ts:3\n    more detail\n";
+        let options = Options {
+            pattern: Some(r"^(?P<file>[^:]+):(?P<line>\d+)$".to_string()),
+            report: Some(Report::Checkstyle),
+            ..include(&[])
+        };
+
+        let (output, _) = run(input, &options);
+
+        assert!(output.contains("message=\"more detail\""));
+    }
+
+    #[test]
+    fn test_nothing_kept_writes_empty_report() {
+        let input = "node_modules/lib/index.d.ts(2,3): error TS2300: Duplicate identifier 'x'.\n";
+
+        let (output, verdict) = run(input, &Options { report: Some(Report::Checkstyle), ..include(&["src/**/*"]) });
+
+        assert_eq!(verdict, Status::Clean);
+        assert_eq!(
+            output,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"4.3\">\n</checkstyle>\n"
+        );
+
+        let (output, _) = run("", &Options { report: Some(Report::Checkstyle), ..include(&[]) });
+        assert!(output.ends_with("<checkstyle version=\"4.3\">\n</checkstyle>\n"));
+    }
+
+    #[test]
+    fn test_pretty_output_and_output_base() {
+        let input = std::fs::read_to_string("tests/data/ansi_tsc_output.txt").unwrap();
+        let options = Options {
+            output_base: Some("src".to_string()),
+            report: Some(Report::Checkstyle),
+            ..include(&["src/**/*"])
+        };
+
+        let (output, _) = run(&input, &options);
+
+        assert!(output.contains("<file name=\"components/Header.tsx\">"));
+        assert!(!output.contains('\u{1b}'));
+        assert!(!output.contains("Found"));
+    }
+
+    #[test]
+    fn test_eslint_json_is_reported_too() {
+        let input = std::fs::read_to_string("tests/data/eslint_json_output.txt").unwrap();
+
+        let (output, _) = run(&input, &Options { report: Some(Report::Checkstyle), ..include(&[]) });
+
+        assert!(output.starts_with("<?xml"));
+        assert!(output.contains("<file name=\"/home/me/project/src/features/orders/index.ts\">"));
+        assert!(output.contains(
+            "<error line=\"10\" column=\"15\" severity=\"warning\" message=\"Unexpected any. Specify a different type.\" source=\"@typescript-eslint/no-explicit-any\"/>"
+        ));
+    }
+}
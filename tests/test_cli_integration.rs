@@ -21,10 +21,17 @@ mod cli_integration_tests {
     }
 
     fn run_tsef_with_input(input: &str, args: &[&str]) -> (String, String, i32) {
+        run_tsef_with_env(input, args, &[])
+    }
+
+    fn run_tsef_with_env(input: &str, args: &[&str], env: &[(&str, &str)]) -> (String, String, i32) {
         let binary_path = get_binary_path();
-        
+
         let mut cmd = Command::new(&binary_path)
             .args(args)
+            .env_remove("NO_COLOR")
+            .env_remove("FORCE_COLOR")
+            .envs(env.iter().copied())
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -34,7 +41,11 @@ mod cli_integration_tests {
         // Write input to stdin
         if let Some(stdin) = cmd.stdin.take() {
             let mut stdin = stdin;
-            stdin.write_all(input.as_bytes()).expect("Failed to write to stdin");
+            // tsef exits without reading its input on usage errors
+            match stdin.write_all(input.as_bytes()) {
+                Err(error) if error.kind() == std::io::ErrorKind::BrokenPipe => {}
+                result => result.expect("Failed to write to stdin"),
+            }
         }
         
         let output = cmd.wait_with_output().expect("Failed to read output");
@@ -69,6 +80,38 @@ mod cli_integration_tests {
         assert!(stdout.contains("Found 2 errors"));
     }
 
+    #[test]
+    fn test_nothing_kept_without_include_is_clean() {
+        let input = std::fs::read_to_string("tests/data/simple_tsc_output.txt").unwrap();
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(&input, &["--code", "TS9999"]);
+
+        assert_eq!(exit_code, 0);
+        assert_eq!(stdout, "");
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(&input, &["--code", "TS9999", "--show-full"]);
+
+        assert_eq!(exit_code, 0);
+        assert_eq!(stdout, "Found 4 errors.\n");
+    }
+
+    #[test]
+    fn test_everything_suppressed_is_clean() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_string_lossy().to_string();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/orders.ts"), "// tsef-ignore TS2345\nprocessOrder(\"123\");\n").unwrap();
+        let input = format!(
+            "{}/src/orders.ts(2,14): error TS2345: Argument of type 'string' is not assignable to parameter of type 'number'.\nFound 1 error.\n",
+            root
+        );
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(&input, &["--root", &root]);
+
+        assert_eq!(exit_code, 0);
+        assert_eq!(stdout, "");
+    }
+
     #[test]
     fn test_filter_by_exact_file() {
         let input = "src/components/Header.tsx(15,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
@@ -232,4 +275,593 @@ mod cli_integration_tests {
         assert_eq!(exit_code, 1);
         assert!(stdout.contains("src/components/Header.tsx"));
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_max_errors_keeps_counting() {
+        let input = "src/a.ts(1,1): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/b.ts(2,1): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/c.ts(3,1): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/c.ts(4,1): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     node_modules/x/index.d.ts(5,1): error TS2717: Subsequent property declarations must have the same type.\n";
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(
+            input,
+            &["-i", "src/**/*", "--max-errors", "1"]
+        );
+
+        assert_eq!(exit_code, 1);
+        assert!(stdout.contains("src/a.ts"));
+        assert!(!stdout.contains("src/b.ts"));
+        assert!(!stdout.contains("src/c.ts"));
+        assert!(stdout.contains("…and 3 more errors in 2 files"));
+    }
+
+    #[test]
+    fn test_max_errors_never_cuts_pretty_blocks() {
+        let input = std::fs::read_to_string("tests/data/ansi_tsc_output.txt").unwrap();
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(
+            &input,
+            &["-i", "src/**/*", "--max-errors", "2"]
+        );
+
+        assert_eq!(exit_code, 1);
+        assert!(stdout.contains("src/components/Header.tsx"));
+        assert!(stdout.contains("const count: number = \"hello\";"));
+        assert!(stdout.contains("src/utils/helpers.ts"));
+        assert!(stdout.contains("return undefined;"));
+        assert!(!stdout.contains("processOrder(\"123\");"));
+        assert!(stdout.contains("…and 1 more error in 1 file"));
+    }
+
+    #[test]
+    fn test_max_per_file() {
+        let input = "src/a.ts(1,1): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/a.ts(2,1): error TS2322: Type 'boolean' is not assignable to type 'number'.\n\
+                     src/b.ts(3,1): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(input, &["--max-per-file", "1"]);
+
+        assert_eq!(exit_code, 1);
+        assert!(stdout.contains("src/a.ts(1,1)"));
+        assert!(!stdout.contains("src/a.ts(2,1)"));
+        assert!(stdout.contains("src/b.ts(3,1)"));
+        assert!(stdout.contains("…and 1 more error in 1 file"));
+    }
+
+    #[test]
+    fn test_max_errors_zero_still_fails() {
+        let input = "src/a.ts(1,1): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(input, &["--max-errors", "0"]);
+
+        assert_eq!(exit_code, 1);
+        assert_eq!(stdout.trim(), "…and 1 more error in 1 file");
+    }
+
+    #[test]
+    fn test_invalid_glob_pattern_is_usage_error() {
+        let input = "src/components/Header.tsx(15,7): error TS2322\n";
+
+        let (stdout, stderr, exit_code) = run_tsef_with_input(input, &["-i", "[invalid-glob"]);
+
+        assert_eq!(exit_code, 2);
+        assert_eq!(stdout, "");
+        assert!(stderr.contains("[invalid-glob"));
+    }
+
+    #[test]
+    fn test_unknown_flag_is_usage_error() {
+        let (_stdout, _stderr, exit_code) = run_tsef_with_input("", &["--no-such-flag"]);
+
+        assert_eq!(exit_code, 2);
+    }
+
+    #[test]
+    fn test_unreadable_input_is_parse_error() {
+        let binary_path = get_binary_path();
+        let mut cmd = Command::new(&binary_path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("Failed to start tsef process");
+        cmd.stdin.take().unwrap().write_all(&[0x73, 0x72, 0x63, 0xff, 0xfe, b'\n']).unwrap();
+        let output = cmd.wait_with_output().expect("Failed to read output");
+
+        assert_eq!(output.status.code(), Some(3));
+    }
+
+    #[test]
+    fn test_malformed_pretty_header_is_parse_error() {
+        let input = "\u{001b}[96msrc/a.ts\u{001b}[0m:1:1 - error TS2322: Type 'string' is not assignable to type 'number'.\n\n\
+                     \u{001b}[96m\u{001b}[0m:2:1 - error TS2322: Type 'string' is not assignable to type 'number'.\n";
+
+        let (_stdout, stderr, exit_code) = run_tsef_with_input(input, &[]);
+
+        assert_eq!(exit_code, 3);
+        assert!(stderr.contains("line 3"));
+    }
+
+    #[test]
+    fn test_exit_zero_reports_without_failing() {
+        let input = "src/a.ts(1,1): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(input, &["--exit-zero"]);
+
+        assert_eq!(exit_code, 0);
+        assert!(stdout.contains("src/a.ts"));
+    }
+
+    #[test]
+    fn test_filters_output_of_command() {
+        let (stdout, _stderr, exit_code) = run_tsef_with_input("", &[
+            "-i", "src/**/*", "--",
+            "sh", "-c",
+            "echo \"src/a.ts(1,1): error TS2322: Type.\"; echo \"lib/b.ts(1,1): error TS2322: Type.\"; exit 2",
+        ]);
+
+        assert_eq!(exit_code, 1);
+        assert!(stdout.contains("src/a.ts"));
+        assert!(!stdout.contains("lib/b.ts"));
+    }
+
+    #[test]
+    fn test_crashed_command() {
+        let (stdout, _stderr, exit_code) = run_tsef_with_input("", &[
+            "--", "sh", "-c", "echo \"src/a.ts(1,1): error TS2322: Type.\"; kill -9 $$",
+        ]);
+
+        assert_eq!(exit_code, 4);
+        assert!(stdout.contains("src/a.ts"));
+    }
+
+    #[test]
+    fn test_command_that_cannot_start() {
+        let (_stdout, _stderr, exit_code) = run_tsef_with_input("", &["--", "./no-such-tsc-binary"]);
+
+        assert_eq!(exit_code, 4);
+    }
+
+    #[test]
+    fn test_normalizes_paths_before_matching() {
+        let cwd = std::env::current_dir().unwrap();
+        let input = format!(
+            "./src/a.ts(1,1): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+             {}/src/b.ts(2,1): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+             src\\c.ts(3,1): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+             lib/../src/d.ts(4,1): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+             lib/e.ts(5,1): error TS2322: Type 'string' is not assignable to type 'number'.\n",
+            cwd.display()
+        );
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(&input, &["-i", "src/**/*"]);
+
+        assert_eq!(exit_code, 1);
+        assert!(stdout.contains("./src/a.ts"));
+        assert!(stdout.contains("/src/b.ts"));
+        assert!(stdout.contains("src\\c.ts"));
+        assert!(stdout.contains("lib/../src/d.ts"));
+        assert!(!stdout.contains("lib/e.ts"));
+    }
+
+    #[test]
+    fn test_root_and_output_base() {
+        let input = "packages/web/src/a.ts(1,1): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     packages/api/src/b.ts(2,1): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(
+            input,
+            &["--root", "packages/web", "-i", "src/**/*", "--output-base", "packages"]
+        );
+
+        assert_eq!(exit_code, 1);
+        assert_eq!(stdout.trim(), "web/src/a.ts(1,1): error TS2322: Type 'string' is not assignable to type 'number'.");
+    }
+
+    #[test]
+    fn test_output_base_in_pretty_output() {
+        let input = std::fs::read_to_string("tests/data/ansi_tsc_output.txt").unwrap();
+
+        let (stdout, _stderr, _exit_code) = run_tsef_with_input(
+            &input,
+            &["-i", "src/components/**/*", "--output-base", "src", "--color", "always"]
+        );
+
+        assert!(stdout.contains("\u{001b}[96mcomponents/Header.tsx\u{001b}[0m:"));
+        assert!(!stdout.contains("src/components/Header.tsx"));
+    }
+
+    #[test]
+    fn test_buffer_sorts_output() {
+        let input = "src/utils/helpers.ts(23,3): error TS2322: Type 'undefined' is not assignable to type 'string'.\n\
+                     src/components/Header.tsx(15,7): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(input, &["--buffer"]);
+
+        assert_eq!(exit_code, 1);
+        assert!(stdout.find("src/components/Header.tsx").unwrap() < stdout.find("src/utils/helpers.ts").unwrap());
+    }
+
+    #[test]
+    fn test_filter_by_code() {
+        let input = "src/a.ts(1,1): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/a.ts(2,1): error TS2345: Argument of type 'string' is not assignable to parameter of type 'number'.\n\
+                     src/b.ts(3,1): error TS7006: Parameter 'x' implicitly has an 'any' type.\n";
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(input, &["--code", "TS2322,TS7006"]);
+        assert_eq!(exit_code, 1);
+        assert!(stdout.contains("src/a.ts(1,1)"));
+        assert!(!stdout.contains("src/a.ts(2,1)"));
+        assert!(stdout.contains("src/b.ts(3,1)"));
+
+        let (stdout, _stderr, exit_code) =
+            run_tsef_with_input(input, &["-i", "src/a.ts", "--exclude-code", "TS2322"]);
+        assert_eq!(exit_code, 1);
+        assert!(!stdout.contains("src/a.ts(1,1)"));
+        assert!(stdout.contains("src/a.ts(2,1)"));
+        assert!(!stdout.contains("src/b.ts(3,1)"));
+    }
+
+    #[test]
+    fn test_eslint_rule_as_code() {
+        let input = std::fs::read_to_string("tests/data/eslint_stylish_output.txt").unwrap();
+
+        let (stdout, _stderr, exit_code) =
+            run_tsef_with_input(&input, &["--code", "@typescript-eslint/no-explicit-any"]);
+
+        assert_eq!(exit_code, 1);
+        assert!(stdout.contains("/home/me/project/src/features/orders/index.ts\n  10:15"));
+        assert!(stdout.contains("/home/me/project/src/legacy/api.ts\n   5:10") || stdout.contains("/home/me/project/src/legacy/api.ts\n  5:10"));
+        assert!(!stdout.contains("no-console"));
+        assert!(!stdout.contains("helpers.ts"));
+    }
+
+    #[test]
+    fn test_input_format() {
+        let input = std::fs::read_to_string("tests/data/tsgo_output.txt").unwrap();
+
+        let (stdout, _stderr, exit_code) =
+            run_tsef_with_input(&input, &["--input-format", "tsgo", "-i", "src/utils/**/*"]);
+
+        assert_eq!(exit_code, 1);
+        assert!(stdout.contains("helpers.ts"));
+        assert!(!stdout.contains("Header.tsx"));
+        assert!(!stdout.contains("Found 3 errors"));
+    }
+
+    #[test]
+    fn test_unknown_input_format_is_usage_error() {
+        let (_stdout, stderr, exit_code) = run_tsef_with_input("", &["--input-format", "jslint"]);
+
+        assert_eq!(exit_code, 2);
+        assert!(stderr.contains("Unknown input format \"jslint\""));
+        assert!(stderr.contains("eslint-json"));
+    }
+
+    #[test]
+    fn test_pattern() {
+        let input = "src/a.ts:3:7: error CHK001: Something is off.\n\
+                     lib/b.ts:10:1: warning CHK002: Something else is off.\n";
+        let pattern = r"^(?P<file>[^:]+):(?P<line>\d+):(?P<col>\d+): (?P<severity>\w+) (?P<code>\S+): (?P<message>.*)$";
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(input, &["--pattern", pattern, "-i", "lib/**/*"]);
+
+        assert_eq!(exit_code, 1);
+        assert_eq!(stdout, "lib/b.ts:10:1: warning CHK002: Something else is off.\n");
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_usage_error() {
+        let (_stdout, stderr, exit_code) = run_tsef_with_input("", &["--pattern", "^(?P<file>"]);
+
+        assert_eq!(exit_code, 2);
+        assert!(stderr.contains("Invalid regular expression"));
+    }
+
+    #[test]
+    fn test_color_never_keeps_pretty_layout() {
+        let input = std::fs::read_to_string("tests/data/ansi_tsc_output.txt").unwrap();
+
+        let (stdout, _stderr, exit_code) =
+            run_tsef_with_input(&input, &["-i", "src/**/*", "--color", "never"]);
+
+        assert_eq!(exit_code, 1);
+        assert!(!stdout.contains('\u{001b}'));
+        assert!(stdout.starts_with(
+            "src/components/Header.tsx:15:7 - error TS2322: Type 'string' is not assignable to type 'number'.\n\
+             \n\
+             15 const count: number = \"hello\";\n\
+             \x20  \x20     ~~~~~\n\
+             \n"
+        ));
+    }
+
+    #[test]
+    fn test_color_auto_follows_environment() {
+        let input = std::fs::read_to_string("tests/data/ansi_tsc_output.txt").unwrap();
+
+        // Our output is a pipe here, which is no terminal
+        let (stdout, _stderr, _exit_code) = run_tsef_with_input(&input, &[]);
+        assert!(!stdout.contains('\u{001b}'));
+
+        let (stdout, _stderr, _exit_code) = run_tsef_with_env(&input, &[], &[("FORCE_COLOR", "1")]);
+        assert!(stdout.contains("\u{001b}[96msrc/components/Header.tsx"));
+
+        // Any value forces colors, as long as there is one
+        let (stdout, _stderr, _exit_code) = run_tsef_with_env(&input, &[], &[("FORCE_COLOR", "0")]);
+        assert!(stdout.contains('\u{001b}'));
+
+        let (stdout, _stderr, _exit_code) = run_tsef_with_env(&input, &[], &[("FORCE_COLOR", "")]);
+        assert!(!stdout.contains('\u{001b}'));
+
+        let (stdout, _stderr, _exit_code) =
+            run_tsef_with_env(&input, &["--color", "always"], &[("NO_COLOR", "1")]);
+        assert!(stdout.contains('\u{001b}'));
+    }
+
+    #[test]
+    fn test_quickfix_format() {
+        let input = std::fs::read_to_string("tests/data/ansi_tsc_output.txt").unwrap();
+
+        let (stdout, _stderr, exit_code) =
+            run_tsef_with_input(&input, &["-i", "src/**/*", "--format", "quickfix", "--show-full", "--color", "always"]);
+
+        assert_eq!(exit_code, 1);
+        assert_eq!(
+            stdout,
+            "src/components/Header.tsx:15:7: error TS2322: Type 'string' is not assignable to type 'number'.\n\
+             src/utils/helpers.ts:23:3: error TS2322: Type 'undefined' is not assignable to type 'string'.\n\
+             src/features/orders/index.ts:42:15: error TS2345: Argument of type 'string' is not assignable to parameter of type 'number'.\n"
+        );
+    }
+
+    #[test]
+    fn test_checkstyle_format() {
+        let input = std::fs::read_to_string("tests/data/simple_tsc_output.txt").unwrap();
+
+        let (stdout, _stderr, exit_code) =
+            run_tsef_with_input(&input, &["-i", "src/**/*", "--format", "checkstyle", "--max-errors", "1"]);
+
+        assert_eq!(exit_code, 1);
+        assert!(stdout.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"4.3\">\n"));
+        assert_eq!(stdout.matches("<error ").count(), 1);
+        assert!(!stdout.contains("more error"));
+    }
+
+    #[test]
+    fn test_gitlab_format() {
+        let input = std::fs::read_to_string("tests/data/ansi_tsc_output.txt").unwrap();
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(&input, &["-i", "src/**/*", "--format", "gitlab"]);
+
+        assert_eq!(exit_code, 1);
+        assert!(stdout.starts_with("[\n"));
+        assert_eq!(stdout.matches("\"fingerprint\"").count(), 3);
+        assert!(stdout.contains("\"path\": \"src/components/Header.tsx\""));
+    }
+
+    #[test]
+    fn test_rdjsonl_format() {
+        let input = std::fs::read_to_string("tests/data/simple_tsc_output.txt").unwrap();
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(&input, &["-i", "src/**/*", "--format", "rdjsonl"]);
+
+        assert_eq!(exit_code, 1);
+        assert!(stdout.lines().all(|line| line.starts_with('{') && line.contains("\"source\":{\"name\":\"tsc\"}")));
+    }
+
+    #[test]
+    fn test_markdown_format() {
+        let input = std::fs::read_to_string("tests/data/simple_tsc_output.txt").unwrap();
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(&input, &["-i", "src/**/*", "--format", "markdown"]);
+
+        assert_eq!(exit_code, 1);
+        assert!(stdout.starts_with("### 3 errors in 3 files\n\n4 diagnostics found, 1 hidden by filter.\n"));
+        assert_eq!(stdout.matches("<details>").count(), 3);
+    }
+
+    #[test]
+    fn test_report_files() {
+        let input = std::fs::read_to_string("tests/data/simple_tsc_output.txt").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let markdown = dir.path().join("summary.md");
+        let gitlab = dir.path().join("gl-code-quality-report.json");
+        let markdown_arg = format!("markdown={}", markdown.display());
+        let gitlab_arg = format!("gitlab={}", gitlab.display());
+
+        let (stdout, _stderr, exit_code) =
+            run_tsef_with_input(&input, &["-i", "src/**/*", "--report", &markdown_arg, "--report", &gitlab_arg]);
+
+        assert_eq!(exit_code, 1);
+        assert!(stdout.starts_with("src/components/Header.tsx(15,7): error TS2322"));
+        assert!(std::fs::read_to_string(&markdown).unwrap().starts_with("### 3 errors in 3 files"));
+        assert_eq!(std::fs::read_to_string(&gitlab).unwrap().matches("\"fingerprint\"").count(), 3);
+    }
+
+    #[test]
+    fn test_unknown_report_format_is_usage_error() {
+        let (_stdout, stderr, exit_code) = run_tsef_with_input("", &["--report", "pdf=out.pdf"]);
+
+        assert_eq!(exit_code, 2);
+        assert!(stderr.contains("unknown report format \"pdf\""));
+    }
+
+    #[test]
+    fn test_unwritable_report_is_usage_error() {
+        let input = std::fs::read_to_string("tests/data/simple_tsc_output.txt").unwrap();
+
+        let (stdout, stderr, exit_code) = run_tsef_with_input(&input, &["--report", "sarif=/nonexistent/x.sarif"]);
+
+        assert_eq!(exit_code, 2);
+        assert_eq!(stdout, "");
+        assert!(stderr.contains("Could not write /nonexistent/x.sarif"));
+    }
+
+    #[test]
+    fn test_sarif_and_junit_report_files() {
+        let input = std::fs::read_to_string("tests/data/simple_tsc_output.txt").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let sarif = dir.path().join("out.sarif");
+        let junit = dir.path().join("out.xml");
+        let sarif_arg = format!("sarif={}", sarif.display());
+        let junit_arg = format!("junit={}", junit.display());
+
+        let (_stdout, _stderr, exit_code) =
+            run_tsef_with_input(&input, &["-i", "src/**/*", "--report", &sarif_arg, "--report", &junit_arg]);
+
+        assert_eq!(exit_code, 1);
+        let sarif: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&sarif).unwrap()).unwrap();
+        assert_eq!(sarif["runs"][0]["results"].as_array().unwrap().len(), 3);
+        assert!(std::fs::read_to_string(&junit).unwrap().contains("<testsuites name=\"tsc\" tests=\"3\" failures=\"3\">"));
+    }
+
+    #[test]
+    fn test_dedupe() {
+        let input = std::fs::read_to_string("tests/data/simple_tsc_output.txt").unwrap();
+        let input = format!("{}{}", input, input);
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(&input, &["-i", "src/**/*", "--dedupe"]);
+
+        assert_eq!(exit_code, 1);
+        assert_eq!(stdout.matches("src/components/Header.tsx(15,7)").count(), 1);
+        assert!(stdout.ends_with("Removed 3 duplicate errors\n"));
+    }
+
+    #[test]
+    fn test_report_unused_suppressions() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_string_lossy().to_string();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/orders.ts"),
+            "// tsef-ignore TS2345 reason: the legacy API takes strings\nprocessOrder(\"123\");\n// tsef-ignore TS2322\nconst total = 0;\n",
+        )
+        .unwrap();
+        let input = format!("{}/src/orders.ts(2,14): error TS2345: Argument of type 'string' is not assignable to parameter of type 'number'.\n", root);
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(&input, &["--root", &root, "-i", "src/**/*"]);
+
+        assert_eq!(exit_code, 0);
+        assert_eq!(stdout, "");
+
+        let (stdout, _stderr, exit_code) =
+            run_tsef_with_input(&input, &["--root", &root, "-i", "src/**/*", "--report-unused-suppressions"]);
+
+        assert_eq!(exit_code, 1);
+        assert_eq!(stdout, "src/orders.ts:3: Unused suppression: tsef-ignore TS2322\n");
+    }
+
+    #[test]
+    fn test_report_unused_suppressions_without_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_string_lossy().to_string();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/orders.ts"), "// tsef-ignore TS2322\nconst total = 0;\n").unwrap();
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input("", &["--root", &root, "--report-unused-suppressions"]);
+
+        assert_eq!(exit_code, 1);
+        assert_eq!(stdout, "src/orders.ts:1: Unused suppression: tsef-ignore TS2322\n");
+    }
+
+    #[test]
+    fn test_report_unused_suppressions_eslint_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_string_lossy().to_string();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/app.js"),
+            "// tsef-ignore no-unused-vars\nconst unused = 1;\n// tsef-ignore eqeqeq\nif (a == b) {}\n",
+        )
+        .unwrap();
+        let input = format!(
+            r#"[{{"filePath":"{}/src/app.js","messages":[{{"ruleId":"no-unused-vars","severity":2,"message":"'unused' is assigned a value but never used.","line":2,"column":7}}],"errorCount":1,"warningCount":0}}]"#,
+            root
+        );
+
+        let (stdout, stderr, exit_code) = run_tsef_with_input(&input, &["--root", &root, "--report-unused-suppressions"]);
+
+        assert_eq!(exit_code, 1);
+        assert_eq!(stdout, "");
+        assert!(stderr.contains("src/app.js:3: Unused suppression: tsef-ignore eqeqeq"));
+        assert!(!stderr.contains("no-unused-vars"));
+    }
+
+    #[test]
+    fn test_suppressions_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("suppressions.toml");
+        std::fs::write(
+            &path,
+            "[[suppression]]\npath = \"src/components/**\"\ncode = \"TS2322\"\nowner = \"@ui-team\"\nreason = \"Waiting on the design system types\"\nexpires = 2999-12-31\n\n\
+             [[suppression]]\npath = \"src/utils/**\"\ncode = \"TS2345\"\nowner = \"@platform\"\nreason = \"Until the helpers move\"\nexpires = 2020-01-01\n",
+        )
+        .unwrap();
+        let path = path.to_string_lossy().to_string();
+        let input = "src/components/Header.tsx(15,7): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(input, &["-i", "src/**/*", "--suppressions", &path]);
+
+        assert_eq!(exit_code, 1);
+        assert_eq!(
+            stdout,
+            format!(
+                "{}:8: Suppression of TS2345 in src/utils/** expired on 2020-01-01, owned by @platform: Until the helpers move\n",
+                path
+            )
+        );
+
+        std::fs::write(&path, "[[suppression]]\npath = \"src/**\"\n").unwrap();
+        let (stdout, stderr, exit_code) = run_tsef_with_input(input, &["--suppressions", &path]);
+
+        assert_eq!(exit_code, 2);
+        assert_eq!(stdout, "");
+        assert!(stderr.contains("The suppression on line 1 is missing its code"));
+
+        let (_stdout, stderr, exit_code) = run_tsef_with_input("", &["--suppressions", "does-not-exist.toml"]);
+
+        assert_eq!(exit_code, 2);
+        assert!(stderr.contains("Could not read does-not-exist.toml"));
+    }
+
+    #[test]
+    fn test_expired_suppressions_next_to_a_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("suppressions.toml");
+        std::fs::write(
+            &path,
+            "[[suppression]]\npath = \"src/utils/**\"\ncode = \"TS2345\"\nowner = \"@platform\"\nreason = \"Until the helpers move\"\nexpires = 2020-01-01\n",
+        )
+        .unwrap();
+        let path = path.to_string_lossy().to_string();
+
+        for input in ["", "src/components/Header.tsx(15,7): error TS2322: Type 'string' is not assignable to type 'number'.\n"] {
+            let (stdout, stderr, exit_code) =
+                run_tsef_with_input(input, &["-i", "src/utils/**", "--suppressions", &path, "--format", "gitlab"]);
+
+            assert_eq!(exit_code, 1);
+            assert_eq!(stdout.trim(), "[]");
+            assert!(stderr.contains(":1: Suppression of TS2345 in src/utils/** expired on 2020-01-01"));
+        }
+    }
+
+    #[test]
+    fn test_eslint_json_quickfix() {
+        let input = std::fs::read_to_string("tests/data/eslint_json_output.txt").unwrap();
+
+        let (stdout, _stderr, exit_code) = run_tsef_with_input(&input, &["--format", "quickfix"]);
+
+        assert_eq!(exit_code, 1);
+        assert_eq!(stdout.lines().count(), 3);
+        assert!(stdout.ends_with("/home/me/project/src/utils/helpers.ts:1:1: error: Parsing error: Unexpected token )\n"));
+    }
+
+    #[test]
+    fn test_print_errorformat() {
+        let (stdout, _stderr, exit_code) = run_tsef_with_input("", &["print-errorformat"]);
+
+        assert_eq!(exit_code, 0);
+        assert_eq!(stdout, "set errorformat=%f:%l:%c:\\ %t%*[a-z]:\\ %m,%f:%l:%c:\\ %t%*[a-z]\\ %m\n");
+    }
+}
@@ -0,0 +1,94 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::run_with_outcome;
+use tsef::{Options, Status};
+
+#[cfg(test)]
+mod dedupe_tests {
+    use super::*;
+
+    fn dedupe() -> Options {
+        Options {
+            dedupe: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_exact_duplicates_are_left_out() {
+        let input = "src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     ./src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/a.ts(2,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/a.ts(1,7): error TS2322: Type 'boolean' is not assignable to type 'number'.\n\
+                     src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+
+        let (output, outcome) = run_with_outcome(input, &dedupe());
+
+        assert_eq!(
+            output,
+            "src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+             src/a.ts(2,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+             src/a.ts(1,7): error TS2322: Type 'boolean' is not assignable to type 'number'.\n\
+             Removed 2 duplicate errors\n"
+        );
+        assert_eq!(outcome.total, 5);
+        assert_eq!(outcome.matched, 3);
+        assert_eq!(outcome.duplicates, 2);
+        assert_eq!(outcome.verdict, Status::Errors);
+    }
+
+    #[test]
+    fn test_duplicates_are_kept_by_default() {
+        let input = "src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+
+        let (output, outcome) = run_with_outcome(input, &Options::default());
+
+        assert_eq!(output, input);
+        assert_eq!(outcome.duplicates, 0);
+    }
+
+    #[test]
+    fn test_whole_pretty_blocks_are_left_out() {
+        let block = std::fs::read_to_string("tests/data/ansi_tsc_output.txt").unwrap();
+        let block: String = block.lines().take(5).map(|line| format!("{}\n", line)).collect();
+        let input = format!("{}{}", block, block);
+
+        let (output, _) = run_with_outcome(&input, &dedupe());
+
+        assert_eq!(output, format!("{}Removed 1 duplicate error\n", block));
+    }
+
+    #[test]
+    fn test_duplicates_do_not_use_up_limits() {
+        let input = "src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/b.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+        let options = Options {
+            max_errors: Some(2),
+            ..dedupe()
+        };
+
+        let (output, outcome) = run_with_outcome(input, &options);
+
+        assert!(output.contains("src/b.ts(1,7)"));
+        assert_eq!(outcome.hidden, 0);
+    }
+
+    #[test]
+    fn test_filtered_diagnostics_are_not_duplicates() {
+        let input = "node_modules/a.d.ts(1,7): error TS2300: Duplicate identifier 'x'.\n\
+                     node_modules/a.d.ts(1,7): error TS2300: Duplicate identifier 'x'.\n";
+        let options = Options {
+            include: vec!["src/**/*".to_string()],
+            ..dedupe()
+        };
+
+        let (output, outcome) = run_with_outcome(input, &options);
+
+        assert_eq!(output, "");
+        assert_eq!(outcome.duplicates, 0);
+        assert_eq!(outcome.verdict, Status::Clean);
+    }
+}
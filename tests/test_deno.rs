@@ -0,0 +1,157 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::{filtered, include_under, run};
+use serde_json::Value;
+use tsef::{
+    Options, Render, Report, StateMachine, Status, deno_state_machine::DenoStateMachine,
+    paths::from_file_url,
+};
+
+#[cfg(test)]
+mod deno_tests {
+    use super::*;
+
+    const ROOT: &str = "/home/me/project";
+    const OUTPUT: &str = include_str!("data/deno_check_output.txt");
+
+    #[test]
+    fn test_detect() {
+        assert!(DenoStateMachine::detect("Check file:///home/me/project/main.ts"));
+        assert!(DenoStateMachine::detect("error: TS2322 [ERROR]: Type 'string' is not assignable."));
+        assert!(!DenoStateMachine::detect("src/a.ts(1,1): error TS2322: Type."));
+    }
+
+    #[test]
+    fn test_from_file_url() {
+        assert_eq!(from_file_url("file:///home/me/main.ts"), Some("/home/me/main.ts".to_string()));
+        assert_eq!(from_file_url("file:///C:/project/main.ts"), Some("C:/project/main.ts".to_string()));
+        assert_eq!(from_file_url("file:///home/me/a%20b.ts"), Some("/home/me/a b.ts".to_string()));
+        assert_eq!(from_file_url("/home/me/main.ts"), None);
+    }
+
+    #[test]
+    fn test_location_comes_after_the_message() {
+        let mut sm = DenoStateMachine::new(vec![]);
+        let mut awaiting = Vec::new();
+        let mut diagnostics = Vec::new();
+        for line in OUTPUT.lines() {
+            sm.run(&line.to_string());
+            if sm.starts_diagnostic() {
+                awaiting.push(sm.awaits_location());
+            }
+            if line.trim_start().starts_with("at ") && !line.starts_with("        ") {
+                let diagnostic = sm.diagnostic().unwrap();
+                diagnostics.push((diagnostic.path.clone(), diagnostic.line, diagnostic.code.clone()));
+            }
+        }
+        assert_eq!(awaiting, vec![true, true, true]);
+        assert_eq!(
+            diagnostics,
+            vec![
+                ("file:///home/me/project/main.ts".to_string(), 3, Some("TS2322".to_string())),
+                ("file:///home/me/project/server/routes.ts".to_string(), 12, Some("TS2345".to_string())),
+                ("file:///home/me/project/server/user%20profile.ts".to_string(), 8, Some("TS2304".to_string())),
+            ]
+        );
+        assert!(sm.is_finished());
+    }
+
+    #[test]
+    fn test_related_location_does_not_move_the_diagnostic() {
+        let (output, status) = run(OUTPUT, &include_under(ROOT, &["server/handler.ts"]));
+        assert_eq!(status, Status::Clean);
+        assert_eq!(output, "");
+    }
+
+    #[test]
+    fn test_filter_by_url_path() {
+        let (output, status) = run(OUTPUT, &include_under(ROOT, &["server/**/*"]));
+        assert_eq!(status, Status::Errors);
+        assert!(output.starts_with("TS2345 [ERROR]"));
+        assert!(output.contains("The expected type comes from this parameter."));
+        assert!(output.contains("Cannot find name 'fetchUser'."));
+        assert!(!output.contains("TS2322"));
+        assert!(!output.contains("Check file://"));
+        assert!(!output.contains("Found 3 errors."));
+    }
+
+    #[test]
+    fn test_percent_encoded_path() {
+        let (output, _) = run(OUTPUT, &include_under(ROOT, &["server/user profile.ts"]));
+        assert!(output.starts_with("TS2304 [ERROR]: Cannot find name 'fetchUser'."));
+        assert!(output.contains("    at file:///home/me/project/server/user%20profile.ts:8:20"));
+    }
+
+    #[test]
+    fn test_output_base_replaces_the_url() {
+        let mut options = include_under(ROOT, &["main.ts"]);
+        options.output_base = Some("/home/me/project".to_string());
+        let (output, _) = run(OUTPUT, &options);
+        assert!(output.contains("    at main.ts:3:7\n"));
+    }
+
+    #[test]
+    fn test_buffer_and_show_full() {
+        let options = Options {
+            buffer: true,
+            show_full: true,
+            ..Default::default()
+        };
+        let (output, _) = run(OUTPUT, &options);
+        assert!(output.starts_with("Check file:///home/me/project/main.ts\n"));
+        assert!(output.contains("Found 3 errors.\n\nerror: Type checking failed."));
+        let main = output.find("TS2322").unwrap();
+        let routes = output.find("TS2345").unwrap();
+        let user = output.find("TS2304").unwrap();
+        assert!(main < routes && routes < user);
+    }
+
+    #[test]
+    fn test_unlocated_diagnostic() {
+        let input = "error: TS2322 [ERROR]: Type 'string' is not assignable to type 'number'.\n";
+        let (output, status) = run(input, &include_under(ROOT, &["**/*"]));
+        assert_eq!(status, Status::Clean);
+        assert_eq!(output, "");
+
+        let (output, status) = run(input, &Options::default());
+        assert_eq!(status, Status::Errors);
+        assert_eq!(output, input);
+    }
+
+    #[test]
+    fn test_report_messages_end_before_the_excerpt() {
+        let options = Options {
+            report: Some(Report::Rdjsonl),
+            ..include_under(ROOT, &[])
+        };
+        let diagnostics: Vec<Value> = filtered(OUTPUT, &options)
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d["message"].as_str().unwrap()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Type 'string' is not assignable to type 'number'.",
+                "Argument of type 'string' is not assignable to parameter of type 'number'.",
+                "Cannot find name 'fetchUser'.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_quickfix_paths_are_decoded() {
+        let options = Options {
+            render: Some(Render::Quickfix),
+            ..include_under(ROOT, &["server/**/*"])
+        };
+
+        assert_eq!(
+            filtered(OUTPUT, &options),
+            "/home/me/project/server/routes.ts:12:10: error TS2345: Argument of type 'string' is not assignable to parameter of type 'number'.\n\
+             /home/me/project/server/user profile.ts:8:20: error TS2304: Cannot find name 'fetchUser'.\n"
+        );
+    }
+}
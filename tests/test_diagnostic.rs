@@ -0,0 +1,78 @@
+use tsef::{Diagnostic, Severity, diagnostic::strip_ansi};
+
+#[cfg(test)]
+mod diagnostic_tests {
+    use super::*;
+
+    #[test]
+    fn test_from_plain() {
+        let diagnostic = Diagnostic::from_plain(
+            "src/features/orders/index.ts(42,15): error TS2345: Argument of type 'string' is not assignable.",
+        )
+        .unwrap();
+        assert_eq!(diagnostic.path, "src/features/orders/index.ts");
+        assert_eq!((diagnostic.line, diagnostic.column), (42, 15));
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.code.as_deref(), Some("TS2345"));
+        assert_eq!(diagnostic.message, "Argument of type 'string' is not assignable.");
+    }
+
+    #[test]
+    fn test_from_plain_with_parentheses_in_path() {
+        let diagnostic = Diagnostic::from_plain("src/(group)/page.ts(1,2): error TS1005: ')' expected.").unwrap();
+        assert_eq!(diagnostic.path, "src/(group)/page.ts");
+        assert_eq!((diagnostic.line, diagnostic.column), (1, 2));
+        assert_eq!(diagnostic.message, "')' expected.");
+    }
+
+    #[test]
+    fn test_from_plain_rejects_other_lines() {
+        assert_eq!(Diagnostic::from_plain("Found 4 errors."), None);
+        assert_eq!(Diagnostic::from_plain("  Type 'string' is not assignable (really): ok"), None);
+    }
+
+    #[test]
+    fn test_from_pretty() {
+        let diagnostic = Diagnostic::from_pretty(
+            "src/utils/helpers.ts:23:3 - warning TS6133: 'value' is declared but its value is never read.",
+        )
+        .unwrap();
+        assert_eq!(diagnostic.path, "src/utils/helpers.ts");
+        assert_eq!((diagnostic.line, diagnostic.column), (23, 3));
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert_eq!(diagnostic.code.as_deref(), Some("TS6133"));
+        assert_eq!(diagnostic.message, "'value' is declared but its value is never read.");
+
+        assert_eq!(Diagnostic::from_pretty("Found 4 errors in 4 files."), None);
+    }
+
+    #[test]
+    fn test_from_pretty_ansi_header() {
+        let input = include_str!("data/ansi_tsc_output.txt");
+        let diagnostic = Diagnostic::from_pretty(&strip_ansi(input.lines().next().unwrap())).unwrap();
+        assert_eq!(diagnostic.path, "src/components/Header.tsx");
+        assert_eq!(diagnostic.code.as_deref(), Some("TS2322"));
+        assert_eq!(diagnostic.message, "Type 'string' is not assignable to type 'number'.");
+    }
+
+    #[test]
+    fn test_with_message_without_code() {
+        let diagnostic = Diagnostic::new("src/a.ts", 1, 1).with_message("error: Invalid property value.");
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.code, None);
+        assert_eq!(diagnostic.message, "Invalid property value.");
+
+        let diagnostic = Diagnostic::new("src/a.ts", 1, 1).with_message("Something odd happened");
+        assert_eq!(diagnostic.code, None);
+        assert_eq!(diagnostic.message, "Something odd happened");
+    }
+
+    #[test]
+    fn test_severity_parse() {
+        assert_eq!(Severity::parse("error"), Some(Severity::Error));
+        assert_eq!(Severity::parse("Warn"), Some(Severity::Warning));
+        assert_eq!(Severity::parse("WARNING"), Some(Severity::Warning));
+        assert_eq!(Severity::parse("Hint"), Some(Severity::Info));
+        assert_eq!(Severity::parse("Type"), None);
+    }
+}
@@ -0,0 +1,162 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::{diagnostics, include_under, run};
+use tsef::{
+    Options, Render, Severity, StateMachine, Status, eslint_state_machine::EslintStateMachine,
+};
+
+#[cfg(test)]
+mod eslint_tests {
+    use super::*;
+
+    const ROOT: &str = "/home/me/project";
+    const STYLISH_OUTPUT: &str = include_str!("data/eslint_stylish_output.txt");
+    const UNIX_OUTPUT: &str = include_str!("data/eslint_unix_output.txt");
+    const JSON_OUTPUT: &str = include_str!("data/eslint_json_output.txt");
+
+    #[test]
+    fn test_detect() {
+        assert!(EslintStateMachine::detect("/home/me/project/src/index.ts"));
+        assert!(EslintStateMachine::detect("/home/me/project/src/index.ts:3:7: Oops. [Error/no-console]"));
+        assert!(!EslintStateMachine::detect("/home/me/project/src/index.ts(3,7): error TS2322: Type."));
+        assert!(!EslintStateMachine::detect("src/index.ts"));
+    }
+
+    #[test]
+    fn test_stylish_rules_become_codes() {
+        let mut sm = EslintStateMachine::new(vec![]);
+        let diagnostics = diagnostics(&mut sm, STYLISH_OUTPUT);
+        assert!(sm.is_finished());
+        assert_eq!(diagnostics.len(), 5);
+        assert_eq!(
+            diagnostics[1],
+            (
+                "/home/me/project/src/features/orders/index.ts".to_string(),
+                10,
+                Severity::Warning,
+                Some("@typescript-eslint/no-explicit-any".to_string())
+            )
+        );
+        assert_eq!(diagnostics[3].0, "/home/me/project/src/utils/helpers.ts");
+        assert_eq!(diagnostics[3].3, None);
+    }
+
+    #[test]
+    fn test_unix_rules_become_codes() {
+        let mut sm = EslintStateMachine::new(vec![]);
+        let diagnostics = diagnostics(&mut sm, UNIX_OUTPUT);
+        assert!(sm.is_finished());
+        assert_eq!(diagnostics.len(), 4);
+        assert_eq!(diagnostics[0].3, Some("@typescript-eslint/no-unused-vars".to_string()));
+        assert_eq!(diagnostics[2].2, Severity::Error);
+        assert_eq!(diagnostics[2].3, None);
+    }
+
+    #[test]
+    fn test_stylish_file_names_go_with_kept_problems() {
+        let (output, status) = run(STYLISH_OUTPUT, &include_under(ROOT, &["src/features/**/*"]));
+        assert_eq!(status, Status::Errors);
+        assert!(output.starts_with("\n/home/me/project/src/features/orders/index.ts\n   3:7"));
+        assert!(output.contains("no-console"));
+        assert!(!output.contains("helpers.ts"));
+        assert!(!output.contains("legacy"));
+        assert!(!output.contains("5 problems"));
+    }
+
+    #[test]
+    fn test_stylish_exclude_code() {
+        let mut options = include_under(ROOT, &["src/**/*"]);
+        options.exclude_codes = vec!["@typescript-eslint/no-explicit-any".to_string()];
+        let (output, _) = run(STYLISH_OUTPUT, &options);
+        assert!(!output.contains("no-explicit-any"));
+        assert!(!output.contains("legacy/api.ts"));
+        assert!(output.contains("helpers.ts\n  1:1  error  Parsing error"));
+    }
+
+    #[test]
+    fn test_stylish_show_full() {
+        let mut options = include_under(ROOT, &["src/legacy/**/*"]);
+        options.show_full = true;
+        let (output, _) = run(STYLISH_OUTPUT, &options);
+        assert!(output.ends_with("\n\n✖ 5 problems (3 errors, 2 warnings)\n  0 errors and 1 warning potentially fixable with the `--fix` option.\n\n"));
+    }
+
+    #[test]
+    fn test_stylish_output_base() {
+        let mut options = include_under(ROOT, &["src/legacy/**/*"]);
+        options.output_base = Some("/home/me/project".to_string());
+        let (output, _) = run(STYLISH_OUTPUT, &options);
+        assert!(output.starts_with("\nsrc/legacy/api.ts\n"));
+    }
+
+    #[test]
+    fn test_unix_filter() {
+        let mut options = include_under(ROOT, &["src/**/*"]);
+        options.codes = vec!["@typescript-eslint/no-explicit-any".to_string()];
+        let (output, status) = run(UNIX_OUTPUT, &options);
+        assert_eq!(status, Status::Errors);
+        assert_eq!(output.lines().count(), 2);
+        assert!(output.contains("orders/index.ts:10:15"));
+        assert!(output.contains("legacy/api.ts:5:10"));
+    }
+
+    #[test]
+    fn test_json_stays_json() {
+        let (output, status) = run(JSON_OUTPUT, &include_under(ROOT, &["src/features/**/*"]));
+        assert_eq!(status, Status::Errors);
+        let results: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let results = results.as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["filePath"], "/home/me/project/src/features/orders/index.ts");
+        assert_eq!(results[0]["messages"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_json_counts_are_adjusted() {
+        let mut options = include_under(ROOT, &["src/**/*"]);
+        options.exclude_codes = vec!["@typescript-eslint/no-unused-vars".to_string()];
+        let (output, _) = run(JSON_OUTPUT, &options);
+        let results: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(results[0]["errorCount"], 0);
+        assert_eq!(results[0]["warningCount"], 1);
+        assert_eq!(results[1]["fatalErrorCount"], 1);
+        assert_eq!(results.as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_json_nothing_left() {
+        let (output, status) = run(JSON_OUTPUT, &include_under(ROOT, &["lib/**/*"]));
+        assert_eq!(status, Status::Clean);
+        assert_eq!(output, "");
+    }
+
+    #[test]
+    fn test_json_quickfix() {
+        let options = Options {
+            render: Some(Render::Quickfix),
+            ..include_under(ROOT, &["src/**/*"])
+        };
+        let (output, status) = run(JSON_OUTPUT, &options);
+
+        assert_eq!(status, Status::Errors);
+        assert_eq!(
+            output,
+            "/home/me/project/src/features/orders/index.ts:3:7: error @typescript-eslint/no-unused-vars: 'count' is assigned a value but never used.\n\
+             /home/me/project/src/features/orders/index.ts:10:15: warning @typescript-eslint/no-explicit-any: Unexpected any. Specify a different type.\n\
+             /home/me/project/src/utils/helpers.ts:1:1: error: Parsing error: Unexpected token )\n"
+        );
+    }
+
+    #[test]
+    fn test_json_render_plain() {
+        let options = Options {
+            render: Some(Render::Plain),
+            output_base: Some(ROOT.to_string()),
+            ..include_under(ROOT, &["src/utils/**/*"])
+        };
+        let (output, _) = run(JSON_OUTPUT, &options);
+
+        assert_eq!(output, "src/utils/helpers.ts(1,1): error: Parsing error: Unexpected token )\n");
+    }
+}
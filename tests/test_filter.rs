@@ -0,0 +1,80 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::run_with_outcome;
+use tsef::{Error, Options, Outcome, Status, filter};
+
+#[cfg(test)]
+mod filter_tests {
+    use super::*;
+
+    const SIMPLE_OUTPUT: &str = include_str!("data/simple_tsc_output.txt");
+    const ANSI_OUTPUT: &str = include_str!("data/ansi_tsc_output.txt");
+
+    #[test]
+    fn test_empty_input_is_clean() {
+        let (output, outcome) = run_with_outcome("", &Options::default());
+        assert_eq!(output, "");
+        assert_eq!(outcome, Outcome::default());
+        assert_eq!(outcome.verdict, Status::Clean);
+    }
+
+    #[test]
+    fn test_simple_output_counts() {
+        let options = Options {
+            include: vec!["src/**/*".to_string()],
+            max_errors: Some(2),
+            ..Default::default()
+        };
+        let (output, outcome) = run_with_outcome(SIMPLE_OUTPUT, &options);
+
+        assert_eq!(outcome.verdict, Status::Errors);
+        assert_eq!(outcome.total, 4);
+        assert_eq!(outcome.matched, 3);
+        assert_eq!(outcome.shown, 2);
+        assert_eq!(outcome.hidden, 1);
+        assert!(output.contains("src/components/Header.tsx"));
+        assert!(output.contains("src/utils/helpers.ts"));
+        assert!(!output.contains("src/features/orders/index.ts"));
+    }
+
+    #[test]
+    fn test_ansi_output_counts() {
+        let options = Options {
+            include: vec!["src/features/**/*".to_string()],
+            ..Default::default()
+        };
+        let (output, outcome) = run_with_outcome(ANSI_OUTPUT, &options);
+
+        assert_eq!(outcome.verdict, Status::Errors);
+        assert_eq!(outcome.total, 4);
+        assert_eq!(outcome.matched, 1);
+        assert_eq!(outcome.shown, 1);
+        assert!(output.contains("processOrder"));
+        assert!(!output.contains("Found 4 errors"));
+    }
+
+    #[test]
+    fn test_only_errors_outside_include_is_clean() {
+        let options = Options {
+            include: vec!["tests/**/*".to_string()],
+            ..Default::default()
+        };
+        let (output, outcome) = run_with_outcome(ANSI_OUTPUT, &options);
+
+        assert_eq!(output, "");
+        assert_eq!(outcome.verdict, Status::Clean);
+        assert_eq!(outcome.total, 4);
+        assert_eq!(outcome.matched, 0);
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let options = Options {
+            include: vec!["[invalid-glob".to_string()],
+            ..Default::default()
+        };
+        let result = filter(SIMPLE_OUTPUT.as_bytes(), Vec::new(), &options);
+        assert!(matches!(result, Err(Error::InvalidPattern(_))));
+    }
+}
@@ -0,0 +1,91 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::{filtered, include};
+use serde_json::Value;
+use tsef::{Options, Report, report::normalize_message};
+
+#[cfg(test)]
+mod gitlab_tests {
+    use super::*;
+
+    fn run(input: &str) -> Vec<Value> {
+        let options = Options {
+            report: Some(Report::Gitlab),
+            ..include(&["src/**/*"])
+        };
+        serde_json::from_str(&filtered(input, &options)).unwrap()
+    }
+
+    fn fingerprints(issues: &[Value]) -> Vec<String> {
+        issues.iter().map(|issue| issue["fingerprint"].as_str().unwrap().to_string()).collect()
+    }
+
+    #[test]
+    fn test_issue_fields() {
+        let input = "src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/b.ts(4,2): warning TS6133: 'x' is declared but its value is never read.\n\
+                     node_modules/lib/index.d.ts(2,3): error TS2300: Duplicate identifier 'x'.\n";
+
+        let issues = run(input);
+
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0]["description"], "Type 'string' is not assignable to type 'number'.");
+        assert_eq!(issues[0]["check_name"], "TS2322");
+        assert_eq!(issues[0]["severity"], "major");
+        assert_eq!(issues[0]["location"]["path"], "src/a.ts");
+        assert_eq!(issues[0]["location"]["lines"]["begin"], 1);
+        assert_eq!(issues[1]["severity"], "minor");
+        assert_eq!(issues[0]["fingerprint"].as_str().unwrap().len(), 32);
+    }
+
+    #[test]
+    fn test_fingerprints_survive_moved_lines() {
+        let before = run("src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n");
+        let after = run("src/a.ts(12,3): error TS2322: Type 'string' is not assignable to type 'number'.\n");
+
+        assert_eq!(fingerprints(&before), fingerprints(&after));
+    }
+
+    #[test]
+    fn test_fingerprints_tell_errors_apart() {
+        let input = "src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/a.ts(1,7): error TS2322: Type 'boolean' is not assignable to type 'number'.\n\
+                     src/a.ts(1,7): error TS2345: Type 'string' is not assignable to type 'number'.\n\
+                     src/b.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+
+        let prints = fingerprints(&run(input));
+
+        for (i, print) in prints.iter().enumerate() {
+            assert!(!prints[i + 1..].contains(print));
+        }
+    }
+
+    #[test]
+    fn test_repeated_errors_get_unique_fingerprints() {
+        let input = "src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/a.ts(9,7): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+        let moved = "src/a.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/a.ts(20,7): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+
+        let prints = fingerprints(&run(input));
+
+        assert_ne!(prints[0], prints[1]);
+        assert_eq!(prints, fingerprints(&run(moved)));
+    }
+
+    #[test]
+    fn test_normalize_message() {
+        assert_eq!(
+            normalize_message("Property 'total' is missing.\n  src/types.ts:12:3 - 'total' is declared here."),
+            "Property 'total' is missing. src/types.ts - 'total' is declared here."
+        );
+        assert_eq!(normalize_message("Declared  in lib.d.ts(4,1)"), "Declared in lib.d.ts");
+    }
+
+    #[test]
+    fn test_nothing_kept_is_an_empty_array() {
+        assert!(run("node_modules/lib/index.d.ts(2,3): error TS2300: Duplicate identifier 'x'.\n").is_empty());
+        assert!(run("").is_empty());
+    }
+}
@@ -0,0 +1,94 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::filtered;
+use tsef::{Options, Report};
+
+#[cfg(test)]
+mod html_tests {
+    use super::*;
+
+    fn run(input: &str, options: Options) -> String {
+        filtered(input, &Options {
+            report: Some(Report::Html),
+            ..options
+        })
+    }
+
+    #[test]
+    fn test_excerpt_is_read_from_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/index.ts"),
+            "const a = 1;\nconst b = 2;\nfunction total() {\n\tconst count: number = \"hello\";\n}\n",
+        )
+        .unwrap();
+        let input = format!(
+            "{}/src/index.ts(4,8): error TS2322: Type 'string' is not assignable to type 'number'.\n",
+            dir.path().to_string_lossy()
+        );
+        let options = Options {
+            root: Some(dir.path().to_string_lossy().to_string()),
+            ..Default::default()
+        };
+
+        let output = run(&input, options);
+
+        assert!(output.contains("<h2>src/index.ts <span class=\"count\">1</span></h2>"));
+        assert!(output.contains(
+            "<pre><span class=\"line\">2 | const b = 2;</span>\n\
+             <span class=\"line\">3 | function total() {</span>\n\
+             <span class=\"line hit\">4 | \tconst count: number = &quot;hello&quot;;</span>\n\
+             <span class=\"caret\">  | \t      ^</span>\n\
+             <span class=\"line\">5 | }</span></pre>"
+        ));
+    }
+
+    #[test]
+    fn test_missing_source_leaves_out_the_excerpt() {
+        let input = "src/missing.ts(3,1): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+
+        let output = run(input, Options::default());
+
+        assert!(output.contains("<code>TS2322</code> Type 'string' is not assignable to type 'number'.</p>"));
+        assert!(!output.contains("<pre>"));
+    }
+
+    #[test]
+    fn test_file_tree_counts_directories() {
+        let input = "src/components/Header.tsx(1,1): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/components/Footer.tsx(1,1): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/features/orders/index.ts(1,1): error TS2345: Argument of type 'string' is not assignable.\n\
+                     src/main.ts(1,1): warning TS6133: 'x' is declared but its value is never read.\n";
+
+        let output = run(input, Options::default());
+
+        assert!(output.contains("<summary>src/ <span class=\"count\">4</span></summary>"));
+        assert!(output.contains("<summary>components/ <span class=\"count\">2</span></summary>"));
+        assert!(output.contains("<summary>features/orders/ <span class=\"count\">1</span></summary>"));
+        assert!(output.contains("<a href=\"#file-0\">Footer.tsx</a>"));
+        assert!(output.contains("<section class=\"file\" id=\"file-0\">\n<h2>src/components/Footer.tsx"));
+        assert!(output.contains("<article class=\"diagnostic warning\" data-code=\"TS6133\">"));
+    }
+
+    #[test]
+    fn test_page_is_self_contained() {
+        let input = "src/a.ts(1,7): error TS2322: Type 'Array<string>' is not assignable to type '\"a\" & B'.\n\
+                     node_modules/lib/index.d.ts(2,3): error TS2300: Duplicate identifier 'x'.\n";
+        let options = Options {
+            include: vec!["src/**/*".to_string()],
+            ..Default::default()
+        };
+
+        let output = run(input, options);
+
+        assert!(output.starts_with("<!DOCTYPE html>\n"));
+        assert!(output.ends_with("</html>\n"));
+        assert!(output.contains("<h1>1 error in 1 file</h1>\n<p>2 diagnostics found, 1 hidden by filter.</p>"));
+        assert!(output.contains("Type 'Array&lt;string&gt;' is not assignable to type '&quot;a&quot; &amp; B'."));
+        assert!(output.contains("<input id=\"search\""));
+        assert!(!output.contains("http"));
+        assert!(!output.contains("<link") && !output.contains("src=\""));
+    }
+}
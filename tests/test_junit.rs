@@ -0,0 +1,56 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::{include, run};
+use tsef::{Options, Report, Status};
+
+#[cfg(test)]
+mod junit_tests {
+    use super::*;
+
+    #[test]
+    fn test_a_test_case_per_error() {
+        let input = "src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     lib/b.ts(2,3): warning TS6133: 'x' is declared but its value is never read.\n\
+                     src/a.ts(5,1): error TS1005: ';' expected.\n\
+                     Found 3 errors in 2 files.\n";
+
+        let (output, verdict) = run(input, &Options { report: Some(Report::Junit), ..include(&[]) });
+
+        assert_eq!(verdict, Status::Errors);
+        assert_eq!(
+            output,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <testsuites name=\"tsc\" tests=\"3\" failures=\"3\">\n  \
+               <testsuite name=\"src/a.ts\" tests=\"2\" failures=\"2\">\n    \
+                 <testcase name=\"TS2322 at 1:7\" classname=\"src/a.ts\">\n      \
+                   <failure type=\"error\" message=\"Type &apos;string&apos; is not assignable to type &apos;number&apos;.\"/>\n    \
+                 </testcase>\n    \
+                 <testcase name=\"TS1005 at 5:1\" classname=\"src/a.ts\">\n      \
+                   <failure type=\"error\" message=\"&apos;;&apos; expected.\"/>\n    \
+                 </testcase>\n  \
+               </testsuite>\n  \
+               <testsuite name=\"lib/b.ts\" tests=\"1\" failures=\"1\">\n    \
+                 <testcase name=\"TS6133 at 2:3\" classname=\"lib/b.ts\">\n      \
+                   <failure type=\"warning\" message=\"&apos;x&apos; is declared but its value is never read.\"/>\n    \
+                 </testcase>\n  \
+               </testsuite>\n\
+             </testsuites>\n"
+        );
+    }
+
+    #[test]
+    fn test_no_errors_is_an_empty_report() {
+        let input = "node_modules/lib/index.d.ts(2,3): error TS2300: Duplicate identifier 'x'.\n";
+
+        let (output, verdict) = run(input, &Options { report: Some(Report::Junit), ..include(&["src/**/*"]) });
+
+        assert_eq!(verdict, Status::Clean);
+        assert_eq!(
+            output,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <testsuites name=\"tsc\" tests=\"0\" failures=\"0\">\n\
+             </testsuites>\n"
+        );
+    }
+}
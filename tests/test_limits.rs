@@ -0,0 +1,50 @@
+use tsef::limits::Limits;
+
+#[cfg(test)]
+mod limits_tests {
+    use super::*;
+
+    #[test]
+    fn test_no_limits_admits_everything() {
+        let mut limits = Limits::new(None, None);
+        for _ in 0..100 {
+            assert!(limits.admit("src/index.ts"));
+        }
+        assert_eq!(limits.shown(), 100);
+        assert_eq!(limits.hidden(), 0);
+        assert_eq!(limits.summary(), None);
+    }
+
+    #[test]
+    fn test_max_errors_counts_the_rest() {
+        let mut limits = Limits::new(Some(2), None);
+        assert!(limits.admit("src/a.ts"));
+        assert!(limits.admit("src/b.ts"));
+        assert!(!limits.admit("src/b.ts"));
+        assert!(!limits.admit("src/c.ts"));
+        assert!(!limits.admit("src/c.ts"));
+
+        assert_eq!(limits.shown(), 2);
+        assert_eq!(limits.hidden(), 3);
+        assert_eq!(limits.summary().unwrap(), "…and 3 more errors in 2 files");
+    }
+
+    #[test]
+    fn test_max_per_file() {
+        let mut limits = Limits::new(None, Some(1));
+        assert!(limits.admit("src/a.ts"));
+        assert!(!limits.admit("src/a.ts"));
+        assert!(limits.admit("src/b.ts"));
+
+        assert_eq!(limits.summary().unwrap(), "…and 1 more error in 1 file");
+    }
+
+    #[test]
+    fn test_summary_groups_digits() {
+        let mut limits = Limits::new(Some(0), None);
+        for index in 0..19950 {
+            limits.admit(&format!("src/file{}.ts", index % 812));
+        }
+        assert_eq!(limits.summary().unwrap(), "…and 19,950 more errors in 812 files");
+    }
+}
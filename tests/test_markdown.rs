@@ -0,0 +1,117 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::filtered;
+use tsef::{Diagnostic, Options, Outcome, Report, Status, markdown};
+
+#[cfg(test)]
+mod markdown_tests {
+    use super::*;
+
+    fn run(input: &str, options: Options) -> String {
+        filtered(input, &Options {
+            report: Some(Report::Markdown),
+            ..options
+        })
+    }
+
+    fn diagnostic(path: &str, line: usize, message: &str) -> Diagnostic {
+        Diagnostic::new(path, line, 1).with_message(&format!("error TS2322: {}", message))
+    }
+
+    #[test]
+    fn test_report_layout() {
+        let input = "src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/a.ts(5,1): warning TS6133: 'x' is declared but its value is never read.\n\
+                     node_modules/lib/index.d.ts(2,3): error TS2300: Duplicate identifier 'x'.\n";
+        let options = Options {
+            include: vec!["src/**/*".to_string()],
+            ..Default::default()
+        };
+
+        let output = run(input, options);
+
+        assert_eq!(
+            output,
+            "### 1 error and 1 warning in 1 file\n\
+             \n\
+             3 diagnostics found, 1 hidden by filter.\n\
+             \n\
+             <details>\n\
+             <summary><code>src/a.ts</code> (2)</summary>\n\
+             \n\
+             | Line | Code | Message |\n\
+             | ---: | ---- | ------- |\n\
+             | 1:7 | `TS2322` | Type 'string' is not assignable to type 'number'. |\n\
+             | 5:1 | `TS6133` | 'x' is declared but its value is never read. |\n\
+             \n\
+             </details>\n"
+        );
+    }
+
+    #[test]
+    fn test_no_errors() {
+        let input = "node_modules/lib/index.d.ts(2,3): error TS2300: Duplicate identifier 'x'.\n";
+        let options = Options {
+            include: vec!["src/**/*".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(run(input, options), "### 0 errors\n\n1 diagnostic found, 1 hidden by filter.\n");
+        assert_eq!(run("", Options::default()), "### 0 errors\n");
+    }
+
+    #[test]
+    fn test_limits_are_counted() {
+        let input = "src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/a.ts(2,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                     src/b.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+        let options = Options {
+            max_errors: Some(1),
+            ..Default::default()
+        };
+
+        let output = run(input, options);
+
+        assert!(output.starts_with("### 1 error in 1 file\n\n3 diagnostics found, 2 more left out by the limits.\n"));
+    }
+
+    #[test]
+    fn test_messages_cannot_break_the_table() {
+        let input = "src/a.ts(1,7): error TS2322: Type 'A | B' is not assignable to type 'Array<string>'.\n  \
+                     Type 'A' is not assignable to type 'Array<string>'.\n";
+
+        let output = run(input, Options::default());
+
+        assert!(output.contains(
+            "| 1:7 | `TS2322` | Type 'A \\| B' is not assignable to type 'Array&lt;string&gt;'.<br>Type 'A' is not assignable to type 'Array&lt;string&gt;'. |\n"
+        ));
+    }
+
+    #[test]
+    fn test_long_reports_are_cut_between_rows() {
+        let diagnostics: Vec<Diagnostic> = (1..=200)
+            .map(|line| diagnostic(&format!("src/file{}.ts", line % 7), line, "Type 'string' is not assignable to type 'number'."))
+            .collect();
+        let outcome = Outcome {
+            verdict: Status::Errors,
+            total: 200,
+            matched: 200,
+            duplicates: 0,
+            suppressed: 0,
+            shown: 200,
+            hidden: 0,
+        };
+
+        let mut output = Vec::new();
+        markdown::write(&diagnostics, &outcome, 4_000, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.len() <= 4_000);
+        assert!(output.starts_with("### 200 errors in 7 files\n"));
+        let rows = output.lines().filter(|line| line.starts_with("| ") && line.contains("TS2322")).count();
+        assert!(rows > 0 && rows < 200);
+        assert!(output.ends_with(&format!("_…and {} more not shown, the report got too long._\n", 200 - rows)));
+        assert_eq!(output.matches("<details>").count(), output.matches("</details>").count());
+    }
+}
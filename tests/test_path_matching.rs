@@ -1,4 +1,4 @@
-use tsef::path_matches;
+use tsef::{check_patterns, path_matches};
 
 #[cfg(test)]
 mod path_matching_tests {
@@ -117,4 +117,13 @@ mod path_matching_tests {
         // On case-sensitive systems, this should not match
         assert!(!path_matches(&include, "src/components/Header.tsx"));
     }
+
+    #[test]
+    fn test_check_patterns() {
+        assert!(check_patterns(&[]).is_ok());
+        assert!(check_patterns(&["src/**/*".to_string(), "**/*.tsx".to_string()]).is_ok());
+
+        let error = check_patterns(&["src/**/*".to_string(), "[invalid-glob".to_string()]);
+        assert!(error.unwrap_err().to_string().contains("[invalid-glob"));
+    }
 }
\ No newline at end of file
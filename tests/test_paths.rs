@@ -0,0 +1,74 @@
+use std::path::Path;
+use tsef::paths::PathNormalizer;
+
+#[cfg(test)]
+mod paths_tests {
+    use super::*;
+
+    fn normalizer(root: Option<&str>) -> PathNormalizer {
+        PathNormalizer::new(Path::new("/repo/app"), root)
+    }
+
+    #[test]
+    fn test_relative_paths_stay_as_they_are() {
+        let normalizer = normalizer(None);
+        assert_eq!(normalizer.normalize("src/components/Header.tsx"), "src/components/Header.tsx");
+        assert_eq!(normalizer.normalize("node_modules/@types/react/index.d.ts"), "node_modules/@types/react/index.d.ts");
+    }
+
+    #[test]
+    fn test_strips_dot_slash_prefix() {
+        let normalizer = normalizer(None);
+        assert_eq!(normalizer.normalize("./src/index.ts"), "src/index.ts");
+        assert_eq!(normalizer.normalize("././src/./index.ts"), "src/index.ts");
+    }
+
+    #[test]
+    fn test_absolute_paths_become_relative_to_root() {
+        let normalizer = normalizer(None);
+        assert_eq!(normalizer.normalize("/repo/app/src/index.ts"), "src/index.ts");
+
+        // Paths outside of the root stay absolute
+        assert_eq!(normalizer.normalize("/repo/lib/src/index.ts"), "/repo/lib/src/index.ts");
+        assert_eq!(normalizer.normalize("/repo/application/index.ts"), "/repo/application/index.ts");
+    }
+
+    #[test]
+    fn test_collapses_parent_segments() {
+        let normalizer = normalizer(None);
+        assert_eq!(normalizer.normalize("src/features/../utils/helpers.ts"), "src/utils/helpers.ts");
+        assert_eq!(normalizer.normalize("../app/src/index.ts"), "src/index.ts");
+        assert_eq!(normalizer.normalize("../lib/index.ts"), "/repo/lib/index.ts");
+    }
+
+    #[test]
+    fn test_windows_paths() {
+        let normalizer = normalizer(None);
+        assert_eq!(normalizer.normalize("src\\components\\Header.tsx"), "src/components/Header.tsx");
+        assert_eq!(normalizer.normalize(".\\src\\index.ts"), "src/index.ts");
+
+        let normalizer = PathNormalizer::new(Path::new("C:\\repo"), None);
+        assert_eq!(normalizer.normalize("C:\\repo\\src\\index.ts"), "src/index.ts");
+        assert_eq!(normalizer.normalize("c:\\repo\\src\\..\\lib\\index.ts"), "lib/index.ts");
+        assert_eq!(normalizer.normalize("D:\\other\\index.ts"), "D:/other/index.ts");
+    }
+
+    #[test]
+    fn test_custom_root() {
+        let normalizer = normalizer(Some(".."));
+        assert_eq!(normalizer.normalize("src/index.ts"), "app/src/index.ts");
+        assert_eq!(normalizer.normalize("/repo/lib/index.ts"), "lib/index.ts");
+
+        let normalizer = PathNormalizer::new(Path::new("/repo/app"), Some("/repo"));
+        assert_eq!(normalizer.normalize("src/index.ts"), "app/src/index.ts");
+    }
+
+    #[test]
+    fn test_relative_to() {
+        let normalizer = normalizer(None);
+        assert_eq!(normalizer.relative_to("src/index.ts", "/repo"), "app/src/index.ts");
+        assert_eq!(normalizer.relative_to("src/index.ts", "src"), "index.ts");
+        assert_eq!(normalizer.relative_to("src/index.ts", "/repo/lib"), "../app/src/index.ts");
+        assert_eq!(normalizer.relative_to("/repo/app/src/index.ts", "."), "src/index.ts");
+    }
+}
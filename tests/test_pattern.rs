@@ -0,0 +1,105 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::run;
+use tsef::{Error, Options, Severity, StateMachine, Status, filter, pattern_state_machine::PatternStateMachine};
+
+#[cfg(test)]
+mod pattern_tests {
+    use super::*;
+
+    const PATTERN: &str =
+        r"^(?P<file>[^:]+):(?P<line>\d+):(?P<col>\d+): (?P<severity>\w+) (?P<code>\S+): (?P<message>.*)$";
+
+    const INPUT: &str = "checker v2.1\n\
+                         src/a.ts:3:7: error CHK001: Something is off.\n\
+                         \x20   see docs/chk001.md\n\
+                         lib/b.ts:10:1: warning CHK002: Something else is off.\n\
+                         \x20   see docs/chk002.md\n\
+                         2 problems\n";
+
+    fn options(include: &[&str]) -> Options {
+        Options {
+            include: include.iter().map(|p| p.to_string()).collect(),
+            pattern: Some(PATTERN.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_named_groups() {
+        let mut sm = PatternStateMachine::new(PATTERN, None, vec![]).unwrap();
+        sm.run(&"lib/b.ts:10:1: warning CHK002: Something else is off.".to_string());
+        let diagnostic = sm.diagnostic().unwrap();
+        assert_eq!(diagnostic.path, "lib/b.ts");
+        assert_eq!((diagnostic.line, diagnostic.column), (10, 1));
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert_eq!(diagnostic.code.as_deref(), Some("CHK002"));
+        assert_eq!(diagnostic.message, "Something else is off.");
+        assert!(sm.starts_diagnostic());
+
+        sm.run(&"    see docs/chk002.md".to_string());
+        assert!(sm.diagnostic().is_some());
+        assert!(!sm.starts_diagnostic());
+        sm.run(&"2 problems".to_string());
+        assert!(sm.diagnostic().is_none());
+    }
+
+    #[test]
+    fn test_only_file_is_required() {
+        let mut sm = PatternStateMachine::new(r"^ERR (?P<file>\S+)", None, vec![]).unwrap();
+        sm.run(&"ERR src/a.ts".to_string());
+        let diagnostic = sm.diagnostic().unwrap();
+        assert_eq!((diagnostic.line, diagnostic.column), (0, 0));
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.code, None);
+    }
+
+    #[test]
+    fn test_filter() {
+        let (output, status) = run(INPUT, &options(&["src/**/*"]));
+        assert_eq!(status, Status::Errors);
+        assert_eq!(output, "src/a.ts:3:7: error CHK001: Something is off.\n    see docs/chk001.md\n");
+
+        let (output, _) = run(INPUT, &options(&[]));
+        assert_eq!(output, INPUT);
+    }
+
+    #[test]
+    fn test_code_filter() {
+        let mut options = options(&[]);
+        options.exclude_codes = vec!["CHK001".to_string()];
+        let (output, _) = run(INPUT, &options);
+        assert!(!output.contains("src/a.ts"));
+        assert!(!output.contains("chk001.md"));
+        assert!(output.contains("lib/b.ts"));
+    }
+
+    #[test]
+    fn test_continuation_rule() {
+        let input = "src/a.ts:3:7: error CHK001: Something is off.\n\
+                     > const a = 1;\n\
+                     lib/b.ts:10:1: warning CHK002: Something else is off.\n\
+                     > const b = 2;\n";
+        let mut options = options(&["lib/**/*"]);
+        options.continuation = Some("^> ".to_string());
+        let (output, _) = run(input, &options);
+        assert_eq!(output, "lib/b.ts:10:1: warning CHK002: Something else is off.\n> const b = 2;\n");
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        let result = PatternStateMachine::new("^(?P<file>[^:]+", None, vec![]);
+        assert!(matches!(result, Err(Error::InvalidPattern(message)) if message.starts_with("Invalid regular expression")));
+
+        let result = PatternStateMachine::new(r"^(?P<path>\S+)", None, vec![]);
+        assert!(matches!(result, Err(Error::InvalidPattern(message)) if message.contains("no (?P<file>...) group")));
+
+        let options = Options {
+            pattern: Some(PATTERN.to_string()),
+            continuation: Some("(".to_string()),
+            ..Default::default()
+        };
+        assert!(filter(INPUT.as_bytes(), Vec::new(), &options).is_err());
+    }
+}
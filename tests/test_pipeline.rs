@@ -0,0 +1,119 @@
+use std::{cell::RefCell, io::Write, rc::Rc};
+
+use ansi_parser::AnsiSequence;
+use heapless::Vec as HeaplessVec;
+use tsef::{
+    Error, Options, Pipeline, Status, ansi_state_machine::AnsiStateMachine,
+    simple_state_machine::SimpleStateMachine,
+};
+
+#[cfg(test)]
+mod pipeline_tests {
+    use super::*;
+
+    // Keeps track of what has been flushed so far, separately from what has only been written
+    #[derive(Clone, Default)]
+    struct Output {
+        written: Rc<RefCell<Vec<u8>>>,
+        flushed: Rc<RefCell<String>>,
+    }
+
+    impl Write for Output {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.written.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            let written = String::from_utf8(self.written.borrow().clone()).unwrap();
+            *self.flushed.borrow_mut() = written;
+            Ok(())
+        }
+    }
+
+    fn ansi_pipeline(output: Output, options: &Options) -> Pipeline<Output> {
+        let mut vec = HeaplessVec::new();
+        vec.push(96).unwrap();
+        let sm = AnsiStateMachine::new(AnsiSequence::SetGraphicsMode(vec), options.include.clone());
+        Pipeline::new(Box::new(sm), output, options).unwrap()
+    }
+
+    #[test]
+    fn test_flushes_each_complete_diagnostic() {
+        let output = Output::default();
+        let options = Options { include: vec!["src/**/*".to_string()], ..Default::default() };
+        let mut pipeline = ansi_pipeline(output.clone(), &options);
+
+        pipeline.push("\u{001b}[96msrc/a.ts\u{001b}[0m:1:1 - error TS2322: Type 'string' is not assignable.").unwrap();
+        pipeline.push("").unwrap();
+        pipeline.push("\u{001b}[7m1\u{001b}[0m const a: number = \"a\";").unwrap();
+        pipeline.push("").unwrap();
+        assert_eq!(*output.flushed.borrow(), "");
+
+        // The next diagnostic tells us the first one is complete, even if it gets filtered out
+        pipeline.push("\u{001b}[96mlib/b.ts\u{001b}[0m:1:1 - error TS2322: Type 'string' is not assignable.").unwrap();
+        assert!(output.flushed.borrow().contains("const a: number"));
+        assert!(!output.flushed.borrow().contains("lib/b.ts"));
+
+        assert_eq!(pipeline.finish().unwrap().verdict, Status::Errors);
+    }
+
+    #[test]
+    fn test_buffer_sorts_by_file() {
+        let output = Output::default();
+        let options = Options { buffer: true, ..Default::default() };
+        let sm = SimpleStateMachine::new(vec![]);
+        let mut pipeline = Pipeline::new(Box::new(sm), output.clone(), &options).unwrap();
+
+        pipeline.push("src/b.ts(3,1): error TS2322: Type 'string' is not assignable.").unwrap();
+        pipeline.push("src/a.ts(9,1): error TS2322: Type 'string' is not assignable.").unwrap();
+        pipeline.push("  Type 'string' is not assignable to type 'number'.").unwrap();
+        pipeline.push("src/a.ts(2,1): error TS2322: Type 'string' is not assignable.").unwrap();
+        pipeline.push("Found 3 errors.").unwrap();
+        assert_eq!(*output.flushed.borrow(), "");
+
+        assert_eq!(pipeline.finish().unwrap().verdict, Status::Errors);
+        assert_eq!(
+            *output.flushed.borrow(),
+            "src/a.ts(2,1): error TS2322: Type 'string' is not assignable.\n\
+             src/a.ts(9,1): error TS2322: Type 'string' is not assignable.\n  \
+             Type 'string' is not assignable to type 'number'.\n\
+             src/b.ts(3,1): error TS2322: Type 'string' is not assignable.\n\
+             Found 3 errors.\n"
+        );
+    }
+
+    #[test]
+    fn test_buffer_keeps_blank_lines_in_place() {
+        let output = Output::default();
+        let options = Options { buffer: true, show_full: true, ..Default::default() };
+        let mut pipeline = ansi_pipeline(output.clone(), &options);
+
+        let input = std::fs::read_to_string("tests/data/ansi_tsc_output.txt").unwrap();
+        for line in input.lines() {
+            pipeline.push(line).unwrap();
+        }
+        pipeline.finish().unwrap();
+
+        let flushed = output.flushed.borrow();
+        let header = flushed.find("node_modules/@types/react/index.d.ts").unwrap();
+        let orders = flushed.find("src/features/orders/index.ts").unwrap();
+        let helpers = flushed.find("src/utils/helpers.ts").unwrap();
+        assert!(header < orders && orders < helpers);
+        assert!(flushed.contains("~~~~~~\u{001b}[0m\n\n\n\nFound 4 errors in 4 files."));
+        assert_eq!(flushed.lines().count(), input.lines().count());
+    }
+
+    #[test]
+    fn test_parse_errors_know_their_line() {
+        let options = Options::default();
+        let mut pipeline = ansi_pipeline(Output::default(), &options);
+
+        pipeline.push("\u{001b}[96msrc/a.ts\u{001b}[0m:1:1 - error TS2322: Type.").unwrap();
+        pipeline.push("").unwrap();
+        match pipeline.push("\u{001b}[96m\u{001b}[0m:2:1 - error TS2322: Type.") {
+            Err(Error::Parse { line, .. }) => assert_eq!(line, 3),
+            _ => panic!("Expected a parse error"),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::{filtered, include};
+use serde_json::Value;
+use tsef::{Options, Report, report::code_url};
+
+#[cfg(test)]
+mod rdjson_tests {
+    use super::*;
+
+    fn run(input: &str, report: Report) -> String {
+        let options = Options {
+            report: Some(report),
+            ..include(&["src/**/*"])
+        };
+        filtered(input, &options)
+    }
+
+    const INPUT: &str = "src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                         node_modules/lib/index.d.ts(2,3): error TS2300: Duplicate identifier 'x'.\n\
+                         src/b.ts(4,2): warning TS6133: 'x' is declared but its value is never read.\n";
+
+    #[test]
+    fn test_rdjson_document() {
+        let document: Value = serde_json::from_str(&run(INPUT, Report::Rdjson)).unwrap();
+
+        assert_eq!(document["source"]["name"], "tsc");
+        let diagnostics = document["diagnostics"].as_array().unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0],
+            serde_json::json!({
+                "message": "Type 'string' is not assignable to type 'number'.",
+                "location": {
+                    "path": "src/a.ts",
+                    "range": { "start": { "line": 1, "column": 7 } }
+                },
+                "severity": "ERROR",
+                "source": { "name": "tsc" },
+                "code": { "value": "TS2322", "url": "https://typescript.tv/errors/#ts2322" }
+            })
+        );
+        assert_eq!(diagnostics[1]["severity"], "WARNING");
+    }
+
+    #[test]
+    fn test_rdjsonl_writes_a_line_per_diagnostic() {
+        let output = run(INPUT, Report::Rdjsonl);
+
+        let lines: Vec<Value> = output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1]["location"]["path"], "src/b.ts");
+        assert_eq!(lines[1]["code"]["value"], "TS6133");
+
+        assert_eq!(run("", Report::Rdjsonl), "");
+    }
+
+    #[test]
+    fn test_diagnostics_without_line_have_no_range() {
+        let input = "src/a.ts: file is too large to check\n";
+        let options = Options {
+            pattern: Some(r"^(?P<file>[^:]+): (?P<message>.*)$".to_string()),
+            report: Some(Report::Rdjsonl),
+            ..Default::default()
+        };
+        let output = filtered(input, &options);
+
+        let line: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(line["location"], serde_json::json!({ "path": "src/a.ts" }));
+        assert_eq!(line["source"]["name"], "tsef");
+        assert!(line.get("code").is_none());
+    }
+
+    #[test]
+    fn test_eslint_source_and_rule_urls() {
+        let input = std::fs::read_to_string("tests/data/eslint_unix_output.txt").unwrap();
+        let options = Options {
+            report: Some(Report::Rdjsonl),
+            ..Default::default()
+        };
+        let output = filtered(&input, &options);
+
+        let first: Value = serde_json::from_str(output.lines().next().unwrap()).unwrap();
+        assert_eq!(first["source"]["name"], "eslint");
+    }
+
+    #[test]
+    fn test_code_urls() {
+        assert_eq!(code_url("TS2345", "tsc").unwrap(), "https://typescript.tv/errors/#ts2345");
+        assert_eq!(code_url("NG8001", "angular").unwrap(), "https://angular.dev/errors/NG8001");
+        assert_eq!(code_url("no-console", "eslint").unwrap(), "https://eslint.org/docs/latest/rules/no-console");
+        assert_eq!(
+            code_url("@typescript-eslint/no-explicit-any", "eslint").unwrap(),
+            "https://typescript-eslint.io/rules/no-explicit-any"
+        );
+        assert_eq!(code_url("react/jsx-key", "eslint"), None);
+        assert_eq!(code_url("no-console", "my-checker"), None);
+        assert_eq!(code_url("TS", "tsc"), None);
+    }
+}
@@ -0,0 +1,120 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::include;
+use tsef::{
+    Diagnostic, DiagnosticParser, Error, Options, Registry, State, StateMachine, Status, filter_with,
+};
+
+#[cfg(test)]
+mod registry_tests {
+    use super::*;
+
+    // An in-house tool printing `ERR path:line message`, with a `lint-tool` banner
+    struct LintTool;
+
+    struct LintToolStateMachine {
+        state: State,
+        diagnostic: Option<Diagnostic>,
+    }
+
+    impl DiagnosticParser for LintTool {
+        fn name(&self) -> &str {
+            "lint-tool"
+        }
+
+        fn detect(&self, first_line: &str) -> bool {
+            first_line.starts_with("lint-tool")
+        }
+
+        fn state_machine(&self, _first_line: &str, _include: Vec<String>) -> Box<dyn StateMachine> {
+            Box::new(LintToolStateMachine {
+                state: State::ParseToPause,
+                diagnostic: None,
+            })
+        }
+    }
+
+    impl StateMachine for LintToolStateMachine {
+        fn is_finished(&self) -> bool {
+            false
+        }
+
+        fn run(&mut self, line: &String) -> (&State, bool) {
+            self.diagnostic = line.strip_prefix("ERR ").and_then(|rest| {
+                let (location, message) = rest.split_once(' ')?;
+                let (path, line) = location.split_once(':')?;
+                let mut diagnostic = Diagnostic::new(path, line.parse().ok()?, 0);
+                diagnostic.message = message.to_string();
+                Some(diagnostic)
+            });
+            (&self.state, true)
+        }
+
+        fn diagnostic(&self) -> Option<&Diagnostic> {
+            self.diagnostic.as_ref()
+        }
+
+        fn starts_diagnostic(&self) -> bool {
+            self.diagnostic.is_some()
+        }
+    }
+
+    const INPUT: &str = "lint-tool 1.0\nERR src/a.ts:3 Bad thing\nERR lib/b.ts:4 Other thing\n";
+
+    fn run(input: &str, options: &Options, registry: &Registry) -> (String, Status) {
+        let mut output = Vec::new();
+        let outcome = filter_with(input.as_bytes(), &mut output, options, registry).unwrap();
+        (String::from_utf8(output).unwrap(), outcome.verdict)
+    }
+
+    #[test]
+    fn test_default_names() {
+        let registry = Registry::default();
+        assert_eq!(
+            registry.names(),
+            vec!["svelte-check", "deno", "angular", "eslint", "tsgo", "vue-tsc", "tsc", "eslint-json"]
+        );
+        assert!(registry.knows("eslint-json"));
+        assert!(!registry.knows("lint-tool"));
+    }
+
+    #[test]
+    fn test_detect_falls_back_to_tsc() {
+        let registry = Registry::default();
+        assert_eq!(registry.detect("src/a.ts(1,1): error TS2322: Type.").unwrap().name(), "tsc");
+        assert_eq!(registry.detect("Check file:///home/me/main.ts").unwrap().name(), "deno");
+    }
+
+    #[test]
+    fn test_registered_parser_is_detected() {
+        let mut registry = Registry::default();
+        registry.register(LintTool);
+        assert_eq!(registry.detect("lint-tool 1.0").unwrap().name(), "lint-tool");
+
+        let (output, status) = run(INPUT, &include(&["src/**/*"]), &registry);
+        assert_eq!(status, Status::Errors);
+        assert_eq!(output, "lint-tool 1.0\nERR src/a.ts:3 Bad thing\n");
+    }
+
+    #[test]
+    fn test_registered_parser_by_name() {
+        let mut registry = Registry::default();
+        registry.register(LintTool);
+        let mut options = include(&["lib/**/*"]);
+        options.input_format = Some("lint-tool".to_string());
+
+        let (output, _) = run("ERR src/a.ts:3 Bad thing\nERR lib/b.ts:4 Other thing\n", &options, &registry);
+        assert_eq!(output, "ERR lib/b.ts:4 Other thing\n");
+    }
+
+    #[test]
+    fn test_unknown_name() {
+        let options = Options {
+            input_format: Some("lint-tool".to_string()),
+            ..Default::default()
+        };
+        let result = filter_with(INPUT.as_bytes(), Vec::new(), &options, &Registry::default());
+        assert!(matches!(result, Err(Error::UnknownInputFormat(name)) if name == "lint-tool"));
+    }
+}
@@ -0,0 +1,206 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::filtered;
+use regex::Regex;
+use std::io::Write;
+
+use tsef::{Options, Render, render::ERRORFORMAT};
+
+#[cfg(test)]
+mod render_tests {
+    use super::*;
+
+    fn render(style: Render) -> Options {
+        Options {
+            render: Some(style),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_plain_to_pretty_draws_code_frame() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.ts");
+        let mut file = std::fs::File::create(&path).unwrap();
+        writeln!(file, "function total() {{\n\tconst count: number = \"hello\";\n}}").unwrap();
+        let path = path.to_string_lossy();
+
+        let input = format!("{}(2,8): error TS2322: Type 'string' is not assignable to type 'number'.\n", path);
+        let output = filtered(&input, &render(Render::Pretty));
+
+        assert_eq!(
+            output,
+            format!(
+                "\x1b[96m{}\x1b[0m:\x1b[93m2\x1b[0m:\x1b[93m8\x1b[0m - \x1b[91merror\x1b[0m\x1b[90m TS2322: \x1b[0mType 'string' is not assignable to type 'number'.\n\
+                 \n\
+                 \x1b[7m2\x1b[0m \tconst count: number = \"hello\";\n\
+                 \x1b[7m \x1b[0m \t      \x1b[91m~~~~~\x1b[0m\n\
+                 \n",
+                path
+            )
+        );
+    }
+
+    #[test]
+    fn test_string_literals_are_underlined_whole() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.ts");
+        std::fs::write(&path, "const count: number = \"hello\";\n").unwrap();
+
+        let input = format!("{}(1,23): error TS2322: Type 'string' is not assignable to type 'number'.\n", path.to_string_lossy());
+        let output = filtered(&input, &render(Render::Pretty));
+
+        assert!(output.contains("\x1b[91m~~~~~~~\x1b[0m\n"));
+    }
+
+    #[test]
+    fn test_missing_source_leaves_out_the_frame() {
+        let input = "src/missing.ts(3,1): warning TS6133: 'x' is declared but its value is never read.\n\
+                     \x20 It is never used.\n";
+        let output = filtered(input, &render(Render::Pretty));
+
+        assert_eq!(
+            output,
+            "\x1b[96msrc/missing.ts\x1b[0m:\x1b[93m3\x1b[0m:\x1b[93m1\x1b[0m - \x1b[93mwarning\x1b[0m\x1b[90m TS6133: \x1b[0m'x' is declared but its value is never read.\n  It is never used.\n\n"
+        );
+    }
+
+    #[test]
+    fn test_pretty_to_plain() {
+        let input = std::fs::read_to_string("tests/data/ansi_tsc_output.txt").unwrap();
+        let mut options = render(Render::Plain);
+        options.include = vec!["src/**/*".to_string()];
+        options.show_full = true;
+        let output = filtered(&input, &options);
+
+        assert!(output.starts_with(
+            "src/components/Header.tsx(15,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+             src/utils/helpers.ts(23,3): error TS2322: Type 'undefined' is not assignable to type 'string'.\n\
+             src/features/orders/index.ts(42,15): error TS2345: Argument of type 'string' is not assignable to parameter of type 'number'.\n"
+        ));
+        assert!(!output.contains("index.d.ts(1024,9)"));
+        assert!(!output.contains('\x1b'));
+        assert!(output.contains("     1  src/utils/helpers.ts:23"));
+    }
+
+    #[test]
+    fn test_plain_keeps_message_chain() {
+        let input = "src/a.ts(1,7): error TS2322: Type '{ id: string; }' is not assignable to type 'Order'.\n\
+                     \x20 Property 'total' is missing in type '{ id: string; }' but required in type 'Order'.\n\
+                     src/b.ts(2,1): error TS2304: Cannot find name 'x'.\n";
+        let output = filtered(input, &render(Render::Plain));
+
+        assert_eq!(output, input);
+    }
+
+    #[test]
+    fn test_eslint_to_plain() {
+        let input = std::fs::read_to_string("tests/data/eslint_stylish_output.txt").unwrap();
+        let mut options = render(Render::Plain);
+        options.codes = vec!["no-console".to_string()];
+        let output = filtered(&input, &options);
+
+        assert_eq!(
+            output,
+            "/home/me/project/src/features/orders/index.ts(12,1): error no-console: Unexpected console statement\n"
+        );
+    }
+
+    #[test]
+    fn test_output_base() {
+        let input = "/home/me/project/src/a.ts(1,7): error TS2304: Cannot find name 'x'.\n";
+        let mut options = render(Render::Plain);
+        options.output_base = Some("/home/me/project".to_string());
+        let output = filtered(input, &options);
+
+        assert_eq!(output, "src/a.ts(1,7): error TS2304: Cannot find name 'x'.\n");
+    }
+
+    #[test]
+    fn test_buffer() {
+        let input = "src/b.ts(2,1): error TS2304: Cannot find name 'x'.\n\
+                     src/a.ts(1,7): error TS2304: Cannot find name 'y'.\n";
+        let mut options = render(Render::Plain);
+        options.buffer = true;
+        let output = filtered(input, &options);
+
+        assert_eq!(
+            output,
+            "src/a.ts(1,7): error TS2304: Cannot find name 'y'.\nsrc/b.ts(2,1): error TS2304: Cannot find name 'x'.\n"
+        );
+    }
+
+    #[test]
+    fn test_quickfix_joins_message_chain() {
+        let input = "Starting compilation...\n\
+                     src/a.ts(1,7): error TS2322: Type '{ id: string; }' is not assignable to type 'Order'.\n\
+                     \x20 Property 'total' is missing in type '{ id: string; }' but required in type 'Order'.\n\
+                     src/b.ts(2,1): warning TS6133: 'x' is declared but its value is never read.\n";
+        let mut options = render(Render::Quickfix);
+        options.max_errors = Some(1);
+        let output = filtered(input, &options);
+
+        assert_eq!(
+            output,
+            "src/a.ts:1:7: error TS2322: Type '{ id: string; }' is not assignable to type 'Order'. Property 'total' is missing in type '{ id: string; }' but required in type 'Order'.\n"
+        );
+    }
+
+    // What Vim reads out of a line with the errorformat: the file, line, column, error
+    // type and message, going by the first of its formats that matches
+    fn read_quickfix(line: &str) -> Option<(String, String, String, String, String)> {
+        let formats = ERRORFORMAT.strip_prefix("set errorformat=").unwrap();
+        for format in formats.split(',') {
+            let pattern = format
+                .replace("\\ ", " ")
+                .replace("%f", "(.+?)")
+                .replace("%l", "(\\d+)")
+                .replace("%c", "(\\d+)")
+                .replace("%t", "(\\w)")
+                .replace("%*[a-z]", "[a-z]*")
+                .replace("%m", "(.*)");
+            if let Some(captures) = Regex::new(&format!("^{}$", pattern)).unwrap().captures(line) {
+                let group = |index: usize| captures[index].to_string();
+                return Some((group(1), group(2), group(3), group(4), group(5)));
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn test_errorformat_reads_quickfix_lines() {
+        let input = "/home/me/project/src/a.js\n  \
+                     1:1  error  Parsing error: Unexpected token\n  \
+                     2:3  warning  Unexpected console statement  no-console\n";
+        let output = filtered(input, &render(Render::Quickfix));
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines[0], "/home/me/project/src/a.js:1:1: error: Parsing error: Unexpected token");
+        assert_eq!(
+            read_quickfix(lines[0]),
+            Some((
+                "/home/me/project/src/a.js".to_string(),
+                "1".to_string(),
+                "1".to_string(),
+                "e".to_string(),
+                "Parsing error: Unexpected token".to_string()
+            ))
+        );
+        assert_eq!(lines[1], "/home/me/project/src/a.js:2:3: warning no-console: Unexpected console statement");
+        assert_eq!(
+            read_quickfix(lines[1]),
+            Some((
+                "/home/me/project/src/a.js".to_string(),
+                "2".to_string(),
+                "3".to_string(),
+                "w".to_string(),
+                "no-console: Unexpected console statement".to_string()
+            ))
+        );
+        assert_eq!(
+            read_quickfix("src/a.ts:1:7: error TS2322: Type 'string' is not assignable to type 'number'.").unwrap().4,
+            "TS2322: Type 'string' is not assignable to type 'number'."
+        );
+    }
+}
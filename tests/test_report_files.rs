@@ -0,0 +1,119 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::filtered;
+use std::io::{BufReader, Read};
+use tsef::{Error, Options, Render, Report, filter};
+
+#[cfg(test)]
+mod report_files_tests {
+    use super::*;
+
+    fn with_reports(dir: &tempfile::TempDir, options: Options) -> Options {
+        let path = |name: &str| dir.path().join(name).to_string_lossy().to_string();
+        Options {
+            reports: vec![
+                (Report::Rdjsonl, path("report.jsonl")),
+                (Report::Checkstyle, path("checkstyle.xml")),
+            ],
+            ..options
+        }
+    }
+
+    fn read(dir: &tempfile::TempDir, name: &str) -> String {
+        std::fs::read_to_string(dir.path().join(name)).unwrap()
+    }
+
+    #[test]
+    fn test_output_stays_the_same() {
+        let input = std::fs::read_to_string("tests/data/ansi_tsc_output.txt").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let options = Options {
+            include: vec!["src/**/*".to_string()],
+            ..Default::default()
+        };
+
+        let plain = filtered(&input, &options);
+        let reported = filtered(&input, &with_reports(&dir, options));
+
+        assert_eq!(plain, reported);
+        assert_eq!(read(&dir, "report.jsonl").lines().count(), 3);
+        assert_eq!(read(&dir, "checkstyle.xml").matches("<error ").count(), 3);
+    }
+
+    #[test]
+    fn test_reports_along_with_rendering() {
+        let input = "src/a.ts(1,7): error TS2322: Type '{ id: string; }' is not assignable to type 'Order'.\n  \
+                     Property 'total' is missing in type '{ id: string; }'.\n\
+                     src/b.ts(2,3): error TS2345: Argument of type 'string' is not assignable.\n";
+        let dir = tempfile::tempdir().unwrap();
+        let options = Options {
+            render: Some(Render::Quickfix),
+            ..Default::default()
+        };
+
+        let output = filtered(input, &with_reports(&dir, options));
+
+        assert_eq!(output.lines().count(), 2);
+        let first = read(&dir, "report.jsonl").lines().next().unwrap().to_string();
+        assert!(first.contains("\"message\":\"Type '{ id: string; }' is not assignable to type 'Order'.\\nProperty 'total' is missing in type '{ id: string; }'.\""));
+    }
+
+    #[test]
+    fn test_headings_stay_out_of_reports() {
+        let input = std::fs::read_to_string("tests/data/eslint_stylish_output.txt").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let output = filtered(&input, &with_reports(&dir, Options::default()));
+
+        assert_eq!(output, filtered(&input, &Options::default()));
+        let report = read(&dir, "checkstyle.xml");
+        assert!(report.contains("<file name=\""));
+        assert!(!report.contains("&#10;"));
+    }
+
+    #[test]
+    fn test_eslint_json_keeps_writing_json() {
+        let input = std::fs::read_to_string("tests/data/eslint_json_output.txt").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let output = filtered(&input, &with_reports(&dir, Options::default()));
+
+        assert_eq!(output, filtered(&input, &Options::default()));
+        assert!(read(&dir, "report.jsonl").contains("\"source\":{\"name\":\"eslint\"}"));
+    }
+
+    #[test]
+    fn test_empty_input_writes_empty_reports() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let output = filtered("", &with_reports(&dir, Options::default()));
+
+        assert_eq!(output, "");
+        assert_eq!(read(&dir, "report.jsonl"), "");
+        assert!(read(&dir, "checkstyle.xml").ends_with("</checkstyle>\n"));
+    }
+
+    // Input that fails the test if anyone reads it
+    struct Unread;
+
+    impl Read for Unread {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            panic!("The input got read");
+        }
+    }
+
+    #[test]
+    fn test_unwritable_report_fails_before_reading() {
+        let options = Options {
+            reports: vec![(Report::Sarif, "/nonexistent/report.sarif".to_string())],
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+
+        let result = filter(BufReader::new(Unread), &mut output, &options);
+
+        assert!(matches!(result, Err(Error::InvalidReportPath(message)) if message.starts_with("Could not write /nonexistent/report.sarif: ")));
+        assert!(output.is_empty());
+    }
+}
@@ -0,0 +1,90 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::{filtered, include};
+use serde_json::{Value, json};
+use tsef::{
+    Diagnostic, Options, Outcome, Report,
+    report::{self, Context},
+};
+
+#[cfg(test)]
+mod sarif_tests {
+    use super::*;
+
+    fn sarif(input: &str) -> Value {
+        let options = Options {
+            report: Some(Report::Sarif),
+            ..include(&["src/**/*"])
+        };
+        serde_json::from_str(&filtered(input, &options)).unwrap()
+    }
+
+    const INPUT: &str = "src/a.ts(1,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                         node_modules/lib/index.d.ts(2,3): error TS2300: Duplicate identifier 'x'.\n\
+                         src/b.ts(4,2): warning TS6133: 'x' is declared but its value is never read.\n\
+                         src/b.ts(9,1): error TS2322: Type 'number' is not assignable to type 'string'.\n";
+
+    #[test]
+    fn test_sarif_document() {
+        let document = sarif(INPUT);
+
+        assert_eq!(document["version"], "2.1.0");
+        let run = &document["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "tsc");
+        assert_eq!(
+            run["tool"]["driver"]["rules"],
+            json!([
+                { "id": "TS2322", "helpUri": "https://typescript.tv/errors/#ts2322" },
+                { "id": "TS6133", "helpUri": "https://typescript.tv/errors/#ts6133" },
+            ])
+        );
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results[0],
+            json!({
+                "ruleId": "TS2322",
+                "ruleIndex": 0,
+                "level": "error",
+                "message": { "text": "Type 'string' is not assignable to type 'number'." },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": "src/a.ts" },
+                        "region": { "startLine": 1, "startColumn": 7 }
+                    }
+                }]
+            })
+        );
+        assert_eq!(results[1]["level"], "warning");
+        assert_eq!(results[1]["ruleIndex"], 1);
+        assert_eq!(results[2]["ruleIndex"], 0);
+    }
+
+    #[test]
+    fn test_diagnostics_without_location() {
+        let diagnostic = Diagnostic::new("", 0, 0).with_message("error TS6053: File 'missing.ts' not found.");
+        let context = Context {
+            source: "tsc",
+            outcome: &Outcome::default(),
+            base: String::new(),
+        };
+        let mut output = Vec::new();
+
+        report::write(Report::Sarif, &[diagnostic], &context, &mut output).unwrap();
+
+        let document: Value = serde_json::from_slice(&output).unwrap();
+
+        let result = &document["runs"][0]["results"][0];
+        assert_eq!(result["ruleId"], "TS6053");
+        assert!(result.get("locations").is_none());
+    }
+
+    #[test]
+    fn test_empty_report() {
+        let document = sarif("");
+
+        assert_eq!(document["runs"][0]["results"], json!([]));
+        assert_eq!(document["runs"][0]["tool"]["driver"]["rules"], json!([]));
+    }
+}
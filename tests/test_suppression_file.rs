@@ -0,0 +1,234 @@
+use tsef::{
+    Diagnostic, Error, Options, Status, filter,
+    suppression_file::{Date, SuppressionFile},
+};
+
+#[cfg(test)]
+mod suppression_file_tests {
+    use super::*;
+
+    const FILE: &str = "# Reviewed by the platform team\n\
+                        \n\
+                        [[suppression]]\n\
+                        path = \"src/legacy/**\"\n\
+                        code = \"TS2345\"\n\
+                        message = \"type 'string'\"\n\
+                        owner = \"@payments-team\"\n\
+                        reason = \"The legacy API takes order ids as strings\"\n\
+                        expires = 2026-12-31\n\
+                        \n\
+                        [[suppression]]\n\
+                        path = \"src/old/**\"\n\
+                        code = \"TS7006\"\n\
+                        owner = \"@web-team\"\n\
+                        reason = \"Migrating to strict mode\"\n\
+                        expires = \"2026-06-30\"\n";
+
+    fn date(text: &str) -> Date {
+        Date::parse(text).unwrap()
+    }
+
+    fn diagnostic(path: &str, code: &str, message: &str) -> Diagnostic {
+        let mut diagnostic = Diagnostic::new(path, 3, 14);
+        diagnostic.code = Some(code.to_string());
+        diagnostic.message = message.to_string();
+        diagnostic
+    }
+
+    #[test]
+    fn test_parse_entries() {
+        let file = SuppressionFile::parse("tsef-suppressions.toml", FILE, date("2026-10-18")).unwrap();
+
+        assert_eq!(file.entries.len(), 2);
+        let entry = &file.entries[0];
+        assert_eq!(entry.line, 3);
+        assert_eq!(entry.path, "src/legacy/**");
+        assert_eq!(entry.code, "TS2345");
+        assert_eq!(entry.owner, "@payments-team");
+        assert_eq!(entry.expires, date("2026-12-31"));
+        assert_eq!(file.entries[1].line, 11);
+        assert_eq!(file.entries[1].expires.to_string(), "2026-06-30");
+    }
+
+    #[test]
+    fn test_live_entries_suppress() {
+        let mut file = SuppressionFile::parse("tsef-suppressions.toml", FILE, date("2026-10-18")).unwrap();
+        let message = "Argument of type 'string' is not assignable to parameter of type 'number'.";
+
+        assert!(file.suppress("src/legacy/orders.ts", &diagnostic("src/legacy/orders.ts", "TS2345", message)));
+        assert!(!file.suppress("src/orders.ts", &diagnostic("src/orders.ts", "TS2345", message)));
+        assert!(!file.suppress("src/legacy/orders.ts", &diagnostic("src/legacy/orders.ts", "TS2322", message)));
+        let other = "Argument of type 'boolean' is not assignable to parameter of type 'number'.";
+        assert!(!file.suppress("src/legacy/orders.ts", &diagnostic("src/legacy/orders.ts", "TS2345", other)));
+        assert!(file.unused().is_empty());
+    }
+
+    #[test]
+    fn test_expired_entries_stop_suppressing() {
+        let mut file = SuppressionFile::parse("tsef-suppressions.toml", FILE, date("2026-10-18")).unwrap();
+        let message = "Parameter 'a' implicitly has an 'any' type.";
+
+        assert!(!file.suppress("src/old/form.ts", &diagnostic("src/old/form.ts", "TS7006", message)));
+        let expired: Vec<&str> = file.expired().iter().map(|entry| entry.code.as_str()).collect();
+        assert_eq!(expired, vec!["TS7006"]);
+        let unused: Vec<&str> = file.unused().iter().map(|entry| entry.code.as_str()).collect();
+        assert_eq!(unused, vec!["TS2345"]);
+    }
+
+    #[test]
+    fn test_entries_expire_on_their_date() {
+        let file = SuppressionFile::parse("tsef-suppressions.toml", FILE, date("2026-06-29")).unwrap();
+        assert!(file.expired().is_empty());
+
+        let file = SuppressionFile::parse("tsef-suppressions.toml", FILE, date("2026-06-30")).unwrap();
+        assert_eq!(file.expired().len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_files() {
+        let error = |text: &str| match SuppressionFile::parse("tsef-suppressions.toml", text, date("2026-10-18")) {
+            Err(Error::InvalidSuppressions(message)) => message,
+            other => panic!("Expected an invalid suppressions file, got {:?}", other),
+        };
+        let entry = "[[suppression]]\npath = \"src/**\"\ncode = \"TS2345\"\nowner = \"@me\"\nreason = \"Why\"\n";
+
+        assert_eq!(
+            error(entry),
+            "tsef-suppressions.toml: The suppression on line 1 is missing its expiry date"
+        );
+        assert_eq!(
+            error(&format!("{}expires = \"next year\"\n", entry)),
+            "tsef-suppressions.toml: The suppression on line 1 needs a date like 2026-12-31 as its expiry date"
+        );
+        assert_eq!(
+            error("[[suppression]]\npath = \"src/**\"\ncode = \"TS2345\"\nexpires = 2026-12-31\n"),
+            "tsef-suppressions.toml: The suppression on line 1 is missing its owner"
+        );
+        assert_eq!(
+            error(&format!("{}expires = 2026-12-31\nmessage = \"(\"\n", entry)).lines().next().unwrap(),
+            "tsef-suppressions.toml: The suppression on line 1 has an invalid message regex: regex parse error:"
+        );
+        assert_eq!(
+            error(&format!("{}expires = 2026-12-31\nexpiry = 2027-01-01\n", entry)),
+            "tsef-suppressions.toml: The suppression on line 1 has an unknown key \"expiry\""
+        );
+        assert_eq!(
+            error("[[suppressions]]\n"),
+            "tsef-suppressions.toml: Unknown key \"suppressions\", suppressions are listed as [[suppression]]"
+        );
+        assert!(error("[[suppression]\n").starts_with("tsef-suppressions.toml: TOML parse error at line 1"));
+    }
+
+    #[test]
+    fn test_parse_dates() {
+        assert_eq!(date("2026-03-09"), Date { year: 2026, month: 3, day: 9 });
+        assert_eq!(Date::parse("2026-3-9"), None);
+        assert_eq!(Date::parse("2026-13-01"), None);
+        assert_eq!(Date::parse("tomorrow"), None);
+        assert!(Date::today() > date("2025-01-01"));
+    }
+
+    fn run(files: &[(&str, &str)], input: &str, options: Options) -> Result<(String, tsef::Outcome), Error> {
+        let dir = tempfile::tempdir().unwrap();
+        for (path, text) in files {
+            std::fs::write(dir.path().join(path), text).unwrap();
+        }
+        let root = dir.path().to_string_lossy().to_string();
+        let input = input.replace("$ROOT", &root);
+        let options = Options {
+            root: Some(root),
+            ..options
+        };
+        let mut output = Vec::new();
+        let outcome = filter(input.as_bytes(), &mut output, &options)?;
+        Ok((String::from_utf8(output).unwrap(), outcome))
+    }
+
+    const SUPPRESSIONS: &str = "[[suppression]]\n\
+                                path = \"src/legacy/**\"\n\
+                                code = \"TS2345\"\n\
+                                owner = \"@payments-team\"\n\
+                                reason = \"The legacy API takes order ids as strings\"\n\
+                                expires = 2999-12-31\n\
+                                \n\
+                                [[suppression]]\n\
+                                path = \"src/old/**\"\n\
+                                code = \"TS7006\"\n\
+                                owner = \"@web-team\"\n\
+                                reason = \"Migrating to strict mode\"\n\
+                                expires = 2020-06-30\n";
+
+    const INPUT: &str = "$ROOT/src/legacy/orders.ts(3,14): error TS2345: Argument of type 'string' is not assignable to parameter of type 'number'.\n\
+                         $ROOT/src/old/form.ts(2,12): error TS7006: Parameter 'a' implicitly has an 'any' type.\n";
+
+    #[test]
+    fn test_suppressions_file_under_the_root() {
+        let (output, outcome) = run(&[("tsef-suppressions.toml", SUPPRESSIONS)], INPUT, Options::default()).unwrap();
+
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("/src/old/form.ts(2,12): error TS7006: Parameter 'a' implicitly has an 'any' type."));
+        assert_eq!(
+            lines[1],
+            "tsef-suppressions.toml:8: Suppression of TS7006 in src/old/** expired on 2020-06-30, owned by @web-team: Migrating to strict mode"
+        );
+        assert_eq!(outcome.suppressed, 1);
+        assert_eq!(outcome.matched, 1);
+        assert_eq!(outcome.verdict, Status::Errors);
+    }
+
+    #[test]
+    fn test_unused_entries_are_reported() {
+        let options = Options {
+            report_unused_suppressions: true,
+            ..Default::default()
+        };
+        let input = "$ROOT/src/new/page.ts(1,1): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+
+        let (output, _) = run(&[("tsef-suppressions.toml", SUPPRESSIONS)], input, options).unwrap();
+
+        assert!(output.contains(
+            "tsef-suppressions.toml:1: Unused suppression of TS2345 in src/legacy/**, owned by @payments-team: The legacy API takes order ids as strings\n"
+        ));
+        assert!(!output.contains("Unused suppression of TS7006"));
+    }
+
+    #[test]
+    fn test_missing_suppressions_file() {
+        let options = Options {
+            suppressions: Some("does-not-exist.toml".to_string()),
+            ..Default::default()
+        };
+
+        let result = run(&[], INPUT, options.clone());
+
+        assert!(matches!(result, Err(Error::InvalidSuppressions(message)) if message.starts_with("Could not read does-not-exist.toml")));
+
+        let result = run(&[], "", options);
+
+        assert!(matches!(result, Err(Error::InvalidSuppressions(_))));
+    }
+
+    #[test]
+    fn test_expired_entries_fail_without_input() {
+        let (output, outcome) = run(&[("tsef-suppressions.toml", SUPPRESSIONS)], "", Options::default()).unwrap();
+
+        assert_eq!(
+            output,
+            "tsef-suppressions.toml:8: Suppression of TS7006 in src/old/** expired on 2020-06-30, owned by @web-team: Migrating to strict mode\n"
+        );
+        assert_eq!(outcome.verdict, Status::Errors);
+    }
+
+    #[test]
+    fn test_expired_entries_fail_eslint_json() {
+        let input = r#"[{"filePath":"$ROOT/src/legacy/orders.ts","messages":[{"ruleId":"TS2345","severity":2,"message":"Argument of type 'string' is not assignable to parameter of type 'number'.","line":3,"column":14}],"errorCount":1,"warningCount":0}]"#;
+
+        let (output, outcome) = run(&[("tsef-suppressions.toml", SUPPRESSIONS)], input, Options::default()).unwrap();
+
+        // The JSON stays readable, with the expired entry going to stderr
+        assert_eq!(output, "");
+        assert_eq!(outcome.suppressed, 1);
+        assert_eq!(outcome.verdict, Status::Errors);
+    }
+}
@@ -0,0 +1,149 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::run_with_outcome;
+use tsef::{
+    Options, Status,
+    suppressions::{Suppression, parse},
+};
+
+#[cfg(test)]
+mod suppressions_tests {
+    use super::*;
+
+    fn suppression(line: usize, target: Option<usize>, codes: &[&str], text: &str) -> Suppression {
+        Suppression {
+            line,
+            target,
+            codes: codes.iter().map(|c| c.to_string()).collect(),
+            text: text.to_string(),
+            used: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_comments() {
+        let source = "// tsef-ignore TS2345 reason: the legacy API takes strings\n\
+                      processOrder(\"123\");\n\
+                      const total: number = \"0\"; // tsef-ignore TS2322, TS2345\n\
+                      /* tsef-ignore */\n\
+                      // tsef-ignore-file TS7006 -- migrating to strict mode\n\
+                      <!-- tsef-ignore-file NG8001 -->\n\
+                      \t{/* tsef-ignore TS2322 */}\n\
+                      // not a tsef-ignored line\n";
+
+        assert_eq!(
+            parse(source),
+            vec![
+                suppression(1, Some(2), &["TS2345"], "tsef-ignore TS2345 reason: the legacy API takes strings"),
+                suppression(3, Some(3), &["TS2322", "TS2345"], "tsef-ignore TS2322, TS2345"),
+                suppression(4, Some(5), &[], "tsef-ignore"),
+                suppression(5, None, &["TS7006"], "tsef-ignore-file TS7006 -- migrating to strict mode"),
+                suppression(6, None, &["NG8001"], "tsef-ignore-file NG8001"),
+                suppression(7, Some(8), &["TS2322"], "tsef-ignore TS2322"),
+            ]
+        );
+    }
+
+    fn project(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (path, source) in files {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, source).unwrap();
+        }
+        dir
+    }
+
+    fn run(dir: &tempfile::TempDir, input: &str, options: Options) -> (String, tsef::Outcome) {
+        let root = dir.path().to_string_lossy().to_string();
+        let input = input.replace("$ROOT", &root);
+        let options = Options {
+            root: Some(root),
+            ..options
+        };
+        run_with_outcome(&input, &options)
+    }
+
+    const ORDERS: &str = "import { processOrder } from './api';\n\
+                          // tsef-ignore TS2345 reason: the legacy API takes strings\n\
+                          processOrder(\"123\");\n\
+                          const total: number = \"0\";\n";
+
+    #[test]
+    fn test_comments_suppress_matching_diagnostics() {
+        let dir = project(&[("src/orders.ts", ORDERS)]);
+        let input = "$ROOT/src/orders.ts(3,14): error TS2345: Argument of type 'string' is not assignable to parameter of type 'number'.\n\
+                     $ROOT/src/orders.ts(4,7): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+
+        let (output, outcome) = run(&dir, input, Options::default());
+
+        assert_eq!(output.lines().count(), 1);
+        assert!(output.contains("orders.ts(4,7): error TS2322"));
+        assert_eq!(outcome.suppressed, 1);
+        assert_eq!(outcome.matched, 1);
+        assert_eq!(outcome.verdict, Status::Errors);
+    }
+
+    #[test]
+    fn test_other_codes_and_lines_are_not_suppressed() {
+        let dir = project(&[("src/orders.ts", ORDERS)]);
+        let input = "$ROOT/src/orders.ts(3,14): error TS2554: Expected 2 arguments, but got 1.\n\
+                     $ROOT/src/orders.ts(4,7): error TS2345: Argument of type 'string' is not assignable to parameter of type 'number'.\n";
+
+        let (_, outcome) = run(&dir, input, Options::default());
+
+        assert_eq!(outcome.suppressed, 0);
+        assert_eq!(outcome.matched, 2);
+    }
+
+    #[test]
+    fn test_file_comments_suppress_the_whole_file() {
+        let dir = project(&[("src/legacy.ts", "// tsef-ignore-file TS7006\nfunction f(a) {}\nfunction g(b) {}\n")]);
+        let input = "$ROOT/src/legacy.ts(2,12): error TS7006: Parameter 'a' implicitly has an 'any' type.\n\
+                     $ROOT/src/legacy.ts(3,12): error TS7006: Parameter 'b' implicitly has an 'any' type.\n";
+
+        let (output, outcome) = run(&dir, input, Options::default());
+
+        assert_eq!(output, "");
+        assert_eq!(outcome.suppressed, 2);
+        assert_eq!(outcome.verdict, Status::Clean);
+    }
+
+    #[test]
+    fn test_unused_suppressions_are_reported() {
+        let dir = project(&[
+            ("src/orders.ts", ORDERS),
+            ("src/fixed.ts", "// tsef-ignore TS2322\nconst total: number = 0;\n"),
+            ("src/clean.ts", "export const a = 1;\n"),
+            ("node_modules/lib/index.ts", "// tsef-ignore TS2322\n"),
+            ("scripts/build.ts", "// tsef-ignore TS2322\n"),
+        ]);
+        let input = "$ROOT/src/orders.ts(3,14): error TS2345: Argument of type 'string' is not assignable to parameter of type 'number'.\n";
+        let options = Options {
+            include: vec!["src/**/*".to_string()],
+            report_unused_suppressions: true,
+            ..Default::default()
+        };
+
+        let (output, outcome) = run(&dir, input, options);
+
+        assert_eq!(output, "src/fixed.ts:1: Unused suppression: tsef-ignore TS2322\n");
+        assert_eq!(outcome.verdict, Status::Errors);
+    }
+
+    #[test]
+    fn test_no_unused_suppressions() {
+        let dir = project(&[("src/orders.ts", ORDERS)]);
+        let input = "$ROOT/src/orders.ts(3,14): error TS2345: Argument of type 'string' is not assignable to parameter of type 'number'.\n";
+        let options = Options {
+            report_unused_suppressions: true,
+            ..Default::default()
+        };
+
+        let (output, outcome) = run(&dir, input, options);
+
+        assert_eq!(output, "");
+        assert_eq!(outcome.verdict, Status::Clean);
+    }
+}
@@ -0,0 +1,144 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::{filtered, include_under, run};
+use serde_json::Value;
+use tsef::{
+    Options, Render, Report, Severity, StateMachine, Status,
+    svelte_check_state_machine::SvelteCheckStateMachine,
+};
+
+#[cfg(test)]
+mod svelte_check_tests {
+    use super::*;
+
+    const ROOT: &str = "/home/me/project";
+    const HUMAN_OUTPUT: &str = include_str!("data/svelte_check_output.txt");
+    const MACHINE_OUTPUT: &str = include_str!("data/svelte_check_machine_output.txt");
+
+    #[test]
+    fn test_detect() {
+        assert!(SvelteCheckStateMachine::detect("===================================="));
+        assert!(SvelteCheckStateMachine::detect("1590680325583 START \"/home/me/project\""));
+        assert!(!SvelteCheckStateMachine::detect("src/a.ts(1,1): error TS2322: Type."));
+    }
+
+    #[test]
+    fn test_human_diagnostics() {
+        let mut sm = SvelteCheckStateMachine::new(vec![]);
+        let mut diagnostics = Vec::new();
+        for line in HUMAN_OUTPUT.lines() {
+            sm.run(&line.to_string());
+            if sm.starts_diagnostic() {
+                diagnostics.push(None);
+            }
+            if let Some(diagnostic) = sm.diagnostic() {
+                *diagnostics.last_mut().unwrap() = Some(diagnostic.clone());
+            }
+        }
+
+        assert!(sm.is_finished());
+        assert_eq!(diagnostics.len(), 3);
+        let diagnostic = diagnostics[1].as_ref().unwrap();
+        assert_eq!(diagnostic.path, "/home/me/project/src/lib/Card.svelte");
+        assert_eq!((diagnostic.line, diagnostic.column), (3, 1));
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert_eq!(diagnostic.message, "A11y: <img> element should have an alt attribute");
+    }
+
+    #[test]
+    fn test_human_message() {
+        let mut sm = SvelteCheckStateMachine::new(vec![]);
+        sm.run(&"/home/me/project/src/routes/+page.svelte:12:5".to_string());
+        assert!(sm.starts_diagnostic());
+        assert!(sm.awaits_location());
+        sm.run(&"Error: Type 'string' is not assignable to type 'number'. (ts)".to_string());
+        assert!(!sm.starts_diagnostic());
+        assert!(!sm.awaits_location());
+
+        let diagnostic = sm.diagnostic().unwrap();
+        assert_eq!(diagnostic.path, "/home/me/project/src/routes/+page.svelte");
+        assert_eq!((diagnostic.line, diagnostic.column), (12, 5));
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.message, "Type 'string' is not assignable to type 'number'.");
+    }
+
+    #[test]
+    fn test_machine_message() {
+        let mut sm = SvelteCheckStateMachine::new(vec![]);
+        sm.run(&"1590680325583 START \"/home/me/project\"".to_string());
+        assert!(sm.diagnostic().is_none());
+        sm.run(&"1590680326779 ERROR \"node_modules/some-lib/Widget.svelte\" 40:10 \"Cannot find name \\\"foo\\\".\"".to_string());
+
+        let diagnostic = sm.diagnostic().unwrap();
+        assert_eq!(diagnostic.path, "/home/me/project/node_modules/some-lib/Widget.svelte");
+        assert_eq!((diagnostic.line, diagnostic.column), (40, 10));
+        assert_eq!(diagnostic.message, "Cannot find name \"foo\".");
+
+        sm.run(&"1590680326807 COMPLETED 20 FILES 2 ERRORS 1 WARNINGS 3 FILES_WITH_PROBLEMS".to_string());
+        assert!(sm.is_finished());
+    }
+
+    #[test]
+    fn test_filter_human_output() {
+        let (output, status) = run(HUMAN_OUTPUT, &include_under(ROOT, &["src/**/*"]));
+
+        assert_eq!(status, Status::Errors);
+        assert!(output.contains("src/routes/+page.svelte:12:5"));
+        assert!(output.contains("<Counter count=\"5\" />"));
+        assert!(output.contains("src/lib/Card.svelte:3:1"));
+        assert!(!output.contains("Widget.svelte"));
+        assert!(!output.contains("foo = 1;"));
+        assert!(!output.contains("Loading svelte-check"));
+        assert!(!output.contains("svelte-check found"));
+    }
+
+    #[test]
+    fn test_human_output_reports_and_quickfix() {
+        let options = Options {
+            report: Some(Report::Rdjsonl),
+            ..include_under(ROOT, &["src/**/*"])
+        };
+        let diagnostics: Vec<Value> = filtered(HUMAN_OUTPUT, &options)
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0]["message"], "Type 'string' is not assignable to type 'number'.");
+        assert_eq!(diagnostics[0]["severity"], "ERROR");
+        assert_eq!(diagnostics[1]["message"], "A11y: <img> element should have an alt attribute");
+        assert_eq!(diagnostics[1]["severity"], "WARNING");
+
+        let options = Options {
+            render: Some(Render::Quickfix),
+            ..include_under(ROOT, &["src/**/*"])
+        };
+
+        assert_eq!(
+            filtered(HUMAN_OUTPUT, &options),
+            "/home/me/project/src/routes/+page.svelte:12:5: error: Type 'string' is not assignable to type 'number'.\n\
+             /home/me/project/src/lib/Card.svelte:3:1: warning: A11y: <img> element should have an alt attribute\n"
+        );
+    }
+
+    #[test]
+    fn test_filter_machine_output() {
+        let options = Options { show_full: true, ..include_under(ROOT, &["src/routes/**/*"]) };
+        let (output, status) = run(MACHINE_OUTPUT, &options);
+
+        assert_eq!(status, Status::Errors);
+        assert!(output.contains("src/routes/+page.svelte"));
+        assert!(!output.contains("Card.svelte"));
+        assert!(!output.contains("Widget.svelte"));
+        assert!(output.contains("COMPLETED"));
+    }
+
+    #[test]
+    fn test_nothing_matching_is_clean() {
+        let (output, status) = run(MACHINE_OUTPUT, &include_under(ROOT, &["src/stores/**/*"]));
+
+        assert_eq!(status, Status::Clean);
+        assert_eq!(output, "");
+    }
+}
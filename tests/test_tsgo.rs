@@ -0,0 +1,103 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::{include, run};
+use tsef::{
+    Error, Options, StateMachine, Status, filter,
+    tsgo_state_machine::{TsgoStateMachine, is_summary},
+};
+
+#[cfg(test)]
+mod tsgo_tests {
+    use super::*;
+
+    const OUTPUT: &str = include_str!("data/tsgo_output.txt");
+
+    fn tsgo(include: &[&str]) -> Options {
+        Options {
+            include: include.iter().map(|p| p.to_string()).collect(),
+            input_format: Some("tsgo".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_is_summary() {
+        assert!(is_summary("Found 3 errors in 3 files."));
+        assert!(is_summary("Found 1 error in src/index.ts\x1b[2m:3\x1b[0m"));
+        assert!(!is_summary("Found it"));
+        assert!(!is_summary("  The expected type comes from this parameter."));
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let mut sm = TsgoStateMachine::new(vec![]);
+        let mut diagnostics = Vec::new();
+        for line in OUTPUT.lines() {
+            sm.run(&line.to_string());
+            if sm.starts_diagnostic() {
+                let diagnostic = sm.diagnostic().unwrap();
+                diagnostics.push((diagnostic.path.clone(), diagnostic.line, diagnostic.code.clone()));
+            }
+        }
+        assert_eq!(
+            diagnostics,
+            vec![
+                ("src/components/Header.tsx".to_string(), 15, Some("TS2322".to_string())),
+                ("src/features/orders/index.ts".to_string(), 42, Some("TS2345".to_string())),
+                ("src/utils/helpers.ts".to_string(), 23, Some("TS2322".to_string())),
+            ]
+        );
+        assert!(sm.is_finished());
+    }
+
+    #[test]
+    fn test_related_information_stays_with_its_diagnostic() {
+        let (output, status) = run(OUTPUT, &tsgo(&["src/features/**/*"]));
+        assert_eq!(status, Status::Errors);
+        assert!(output.contains("processOrder(\"123\")"));
+        assert!(output.contains("The expected type comes from this parameter."));
+        assert!(!output.contains("Header.tsx"));
+        assert!(!output.contains("helpers.ts"));
+        assert!(!output.contains("Found 3 errors"));
+    }
+
+    #[test]
+    fn test_summary_with_show_full() {
+        let mut options = tsgo(&["src/utils/**/*"]);
+        options.show_full = true;
+        let (output, _) = run(OUTPUT, &options);
+        assert!(output.contains("helpers.ts"));
+        assert!(output.contains("Found 3 errors in 3 files."));
+        assert!(output.contains("Errors  Files"));
+    }
+
+    #[test]
+    fn test_detected_without_input_format() {
+        assert!(TsgoStateMachine::detect(OUTPUT.lines().next().unwrap()));
+        assert!(!TsgoStateMachine::detect(include_str!("data/ansi_tsc_output.txt").lines().next().unwrap()));
+
+        let (output, status) = run(OUTPUT, &include(&["src/utils/**"]));
+        assert_eq!(status, Status::Errors);
+        assert!(output.contains("helpers.ts"));
+        assert!(!output.contains("Found 3 errors"));
+        assert!(!output.contains("Errors  Files"));
+    }
+
+    #[test]
+    fn test_no_match() {
+        let (output, status) = run(OUTPUT, &tsgo(&["lib/**/*"]));
+        assert_eq!(status, Status::Clean);
+        assert_eq!(output, "");
+    }
+
+    #[test]
+    fn test_unknown_input_format() {
+        let options = Options {
+            input_format: Some("tsgoo".to_string()),
+            ..Default::default()
+        };
+        let result = filter(OUTPUT.as_bytes(), Vec::new(), &options);
+        assert!(matches!(result, Err(Error::UnknownInputFormat(name)) if name == "tsgoo"));
+    }
+}
@@ -0,0 +1,81 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use common::{include, run};
+use tsef::{
+    Status, StateMachine, simple_state_machine::SimpleStateMachine,
+    vue_tsc_state_machine::{VueTscStateMachine, component_path},
+};
+
+#[cfg(test)]
+mod vue_tsc_tests {
+    use super::*;
+
+    const VUE_OUTPUT: &str = include_str!("data/vue_tsc_output.txt");
+    const VUE_SIMPLE_OUTPUT: &str = include_str!("data/vue_tsc_simple_output.txt");
+
+    #[test]
+    fn test_component_path() {
+        assert_eq!(component_path("src/App.vue"), "src/App.vue");
+        assert_eq!(component_path("src/App.vue.ts"), "src/App.vue");
+        assert_eq!(component_path("src/App.vue.__VLS_template.ts"), "src/App.vue");
+        assert_eq!(component_path("src/App.vue?vue&type=script&lang.ts"), "src/App.vue");
+        assert_eq!(component_path("src/stores/orders.ts"), "src/stores/orders.ts");
+        assert_eq!(component_path("src/vue.config.ts"), "src/vue.config.ts");
+        assert_eq!(component_path("src/types/Comp.vue.d.ts"), "src/types/Comp.vue.d.ts");
+        assert_eq!(component_path("src/App.vue.js"), "src/App.vue.js");
+    }
+
+    #[test]
+    fn test_declaration_files_keep_their_path() {
+        let input = "src/types/Comp.vue.d.ts(1,1): error TS2300: Duplicate identifier 'Comp'.\n";
+        let (output, status) = run(input, &include(&["src/**/*.ts"]));
+
+        assert_eq!(status, Status::Errors);
+        assert_eq!(output, input);
+    }
+
+    #[test]
+    fn test_maps_virtual_files_to_components() {
+        let sm = SimpleStateMachine::new(vec![]);
+        let mut sm = VueTscStateMachine::new(Box::new(sm));
+
+        sm.run(&"src/components/OrderRow.vue.__VLS_template.ts(3,9): error TS2339: Property 'totl' does not exist.".to_string());
+        let diagnostic = sm.diagnostic().unwrap();
+        assert_eq!(diagnostic.path, "src/components/OrderRow.vue");
+        assert_eq!((diagnostic.line, diagnostic.column), (3, 9));
+    }
+
+    #[test]
+    fn test_pretty_output_include_vue_files() {
+        let (output, status) = run(VUE_OUTPUT, &include(&["src/**/*.vue"]));
+
+        assert_eq!(status, Status::Errors);
+        assert!(output.contains("src/components/OrderList.vue"));
+        assert!(output.contains("<OrderRow :count=\"label\" />"));
+        assert!(output.contains("src/components/OrderRow.vue.__VLS_template.ts"));
+        assert!(output.contains("{{ totl }}"));
+        assert!(output.contains("src/legacy/Checkout.vue.ts"));
+        assert!(!output.contains("src/stores/orders.ts"));
+        assert!(!output.contains("addOrder(null);"));
+    }
+
+    #[test]
+    fn test_plain_output_include_vue_files() {
+        let (output, status) = run(VUE_SIMPLE_OUTPUT, &include(&["src/components/**/*.vue"]));
+
+        assert_eq!(status, Status::Errors);
+        assert!(output.contains("src/components/OrderList.vue(12,5)"));
+        assert!(output.contains("src/components/OrderRow.vue.__VLS_template.ts(3,9)"));
+        assert!(!output.contains("src/stores/orders.ts"));
+        assert!(!output.contains("src/legacy/Checkout.vue.ts"));
+    }
+
+    #[test]
+    fn test_typescript_files_of_vue_projects() {
+        let (output, _status) = run(VUE_OUTPUT, &include(&["src/stores/**/*"]));
+
+        assert!(output.contains("src/stores/orders.ts"));
+        assert!(!output.contains("OrderList.vue"));
+    }
+}